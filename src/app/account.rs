@@ -0,0 +1,214 @@
+use egui::*;
+
+use super::format::*;
+use super::{BackendState, SubApp, SubAppEvent};
+use crate::backend::*;
+
+/// How long a changed balance stays highlighted, in seconds.
+const BALANCE_FLASH: f64 = 1.0;
+
+/// Account sub-app.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AccountApp {
+  last_anchor: String,
+  account: Option<AccountKey>,
+  requested: bool,
+  /// Reload the balance every this many new blocks while the account is open, 0 disables it.
+  refresh_blocks: u32,
+  /// Address typed into the lookup box.
+  #[serde(skip)]
+  lookup: String,
+  /// Best block when the balance was last requested.
+  #[serde(skip)]
+  requested_at: BlockNumber,
+  /// Last balance shown, `None` until loaded.
+  #[serde(skip)]
+  last_balance: Option<Option<AccountBalance>>,
+  #[serde(skip)]
+  changed_at: f64,
+}
+
+impl Default for AccountApp {
+  fn default() -> Self {
+    Self {
+      last_anchor: Default::default(),
+      account: None,
+      requested: false,
+      refresh_blocks: 1,
+      lookup: Default::default(),
+      requested_at: 0,
+      last_balance: None,
+      changed_at: 0.0,
+    }
+  }
+}
+
+impl AccountApp {
+  fn parse_anchor(&mut self, anchor: &str) -> Result<(), String> {
+    if self.last_anchor == anchor {
+      return Ok(());
+    }
+    self.last_anchor = anchor.to_string();
+    self.requested = false;
+    self.last_balance = None;
+    self.account = None;
+    let param = anchor
+      .strip_prefix(self.anchor())
+      .ok_or_else(|| format!("Failed to parse nav anchor: {}", anchor))?;
+    if !param.is_empty() {
+      let account =
+        parse_account(param).ok_or_else(|| format!("Failed to parse account: {param:?}"))?;
+      self.account = Some(account);
+    }
+    Ok(())
+  }
+
+  fn lookup_ui(&mut self, ui: &mut Ui) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    ui.horizontal(|ui| {
+      ui.label("Account:");
+      let resp = ui.add(
+        TextEdit::singleline(&mut self.lookup)
+          .hint_text("SS58 address or 0x public key")
+          .desired_width(400.0),
+      );
+      let account = parse_account(&self.lookup);
+      let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+      if ui
+        .add_enabled(account.is_some(), Button::new("Show"))
+        .clicked()
+        || enter
+      {
+        if let Some(account) = account {
+          self.lookup.clear();
+          app_event = Some(SubAppEvent::OpenApp(account_anchor(&account)));
+        }
+      }
+    });
+    app_event
+  }
+
+  fn account_ui(&mut self, ui: &mut Ui, backend: &mut BackendState, account: &AccountKey) {
+    // Only the open account is refreshed, `update` isn't called for hidden sub-apps.
+    let refresh_due = self.refresh_blocks > 0
+      && backend.best_block.saturating_sub(self.requested_at) >= self.refresh_blocks;
+    if !self.requested || refresh_due {
+      self.requested = true;
+      self.requested_at = backend.best_block;
+      backend.get_account_balance(*account);
+    }
+    // Highlight the balance when a refresh changed it.
+    let now = ui.input(|i| i.time);
+    if let Some(balance) = backend.accounts.get(account) {
+      if self.last_balance.as_ref() != Some(balance) {
+        if self.last_balance.is_some() {
+          self.changed_at = now;
+        }
+        self.last_balance = Some(balance.clone());
+      }
+    }
+    let flash = 1.0 - ((now - self.changed_at) / BALANCE_FLASH).clamp(0.0, 1.0);
+    if flash > 0.0 {
+      ui.ctx().request_repaint();
+    }
+    let highlight = ui.visuals().selection.bg_fill.linear_multiply(flash as f32);
+    let value = |text: String| RichText::new(text).background_color(highlight);
+    let amounts = backend.settings.amount_format;
+    egui::Grid::new("account_info")
+      .num_columns(2)
+      .striped(true)
+      .show(ui, |ui| {
+        ui.label("Address:");
+        ui.horizontal(|ui| {
+          let address = format_account(account, backend.ss58_prefix());
+          ui.label(&address);
+          backend.bookmark_button_ui(ui, &account_anchor(account), || address);
+        });
+        ui.end_row();
+        ui.label("Public key:");
+        ui.label(format!("0x{}", hex::encode(account)));
+        ui.end_row();
+        match backend.accounts.get(account) {
+          Some(Some(balance)) => {
+            ui.label("Nonce:");
+            ui.label(value(balance.nonce.to_string()));
+            ui.end_row();
+            ui.label("Free:");
+            ui.label(value(format_balance(balance.free, amounts)))
+              .on_hover_text(balance.free.to_string());
+            ui.end_row();
+            ui.label("Reserved:");
+            ui.label(value(format_balance(balance.reserved, amounts)))
+              .on_hover_text(balance.reserved.to_string());
+            ui.end_row();
+          }
+          Some(None) => {
+            ui.label("Balance:");
+            ui.weak("Account not found on chain");
+            ui.end_row();
+          }
+          None => {
+            ui.label("Balance:");
+            ui.spinner();
+            ui.end_row();
+          }
+        }
+      });
+    ui.horizontal(|ui| {
+      if ui.button("Refresh").clicked() {
+        self.requested = false;
+      }
+      ui.label("Refresh every");
+      ui.add(DragValue::new(&mut self.refresh_blocks).clamp_range(0..=1000));
+      ui.label("blocks");
+      ui.weak("(0 = off)");
+    });
+    if backend.raw_rpc_ui(ui, RpcSubject::Account(*account)) {
+      self.requested = false;
+    }
+  }
+}
+
+/// Anchor of the account sub-app for `account`.
+pub fn account_anchor(account: &AccountKey) -> String {
+  format!("account/0x{}", hex::encode(account))
+}
+
+impl SubApp for AccountApp {
+  fn name(&self) -> &str {
+    "Account"
+  }
+
+  fn anchor(&self) -> &str {
+    "account/"
+  }
+
+  fn update(
+    &mut self,
+    backend: &mut BackendState,
+    ctx: &egui::Context,
+    anchor: &str,
+  ) -> Option<SubAppEvent> {
+    let res = self.parse_anchor(anchor);
+    let mut app_event = None;
+    egui::CentralPanel::default().show(ctx, |ui| {
+      app_event = self.lookup_ui(ui);
+      ui.separator();
+      match (res, self.account) {
+        (Ok(_), Some(account)) => self.account_ui(ui, backend, &account),
+        (Ok(_), None) => {
+          ui.weak("Enter an account address to look up.");
+        }
+        (Err(err), _) => {
+          ui.label(format!("Failed: {err:?}"));
+        }
+      }
+    });
+    app_event
+  }
+
+  fn reset(&mut self) {
+    *self = Default::default();
+  }
+}
@@ -0,0 +1,93 @@
+use egui::*;
+
+use super::{BackendState, SubAppEvent};
+
+/// Named link to a view, e.g. an account.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Bookmark {
+  pub label: String,
+  pub anchor: String,
+}
+
+impl BackendState {
+  pub fn is_bookmarked(&self, anchor: &str) -> bool {
+    self
+      .bookmarks
+      .iter()
+      .any(|bookmark| bookmark.anchor == anchor)
+  }
+
+  /// Bookmark the view at `anchor`, or remove its bookmark.
+  pub fn toggle_bookmark(&mut self, anchor: &str, label: String) {
+    let len = self.bookmarks.len();
+    self.bookmarks.retain(|bookmark| bookmark.anchor != anchor);
+    if self.bookmarks.len() == len {
+      self.bookmarks.push(Bookmark {
+        label,
+        anchor: anchor.to_string(),
+      });
+    }
+    self.editing_bookmark = None;
+    self.need_save = true;
+  }
+
+  /// Star button bookmarking the view at `anchor`.  `label` is the bookmark's default label.
+  pub fn bookmark_button_ui(&mut self, ui: &mut Ui, anchor: &str, label: impl FnOnce() -> String) {
+    let bookmarked = self.is_bookmarked(anchor);
+    if ui
+      .selectable_label(bookmarked, if bookmarked { "★" } else { "☆" })
+      .on_hover_text(if bookmarked {
+        "Remove bookmark"
+      } else {
+        "Bookmark"
+      })
+      .clicked()
+    {
+      self.toggle_bookmark(anchor, label());
+    }
+  }
+
+  /// Sidebar list of the bookmarks.
+  pub fn bookmarks_ui(&mut self, ui: &mut Ui) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    let mut remove = None;
+    if self.bookmarks.is_empty() {
+      ui.weak("Bookmark accounts with ☆ to keep them here.");
+    }
+    for (idx, bookmark) in self.bookmarks.iter_mut().enumerate() {
+      ui.horizontal(|ui| {
+        if ui.small_button("✖").on_hover_text("Remove").clicked() {
+          remove = Some(idx);
+        }
+        if self.editing_bookmark == Some(idx) {
+          let resp = ui.text_edit_singleline(&mut bookmark.label);
+          if !resp.has_focus() && !resp.lost_focus() {
+            // Just started editing.
+            resp.request_focus();
+          }
+          if resp.lost_focus() {
+            self.editing_bookmark = None;
+            self.need_save = true;
+          }
+          return;
+        }
+        if ui.small_button("✏").on_hover_text("Rename").clicked() {
+          self.editing_bookmark = Some(idx);
+        }
+        if ui
+          .link(&bookmark.label)
+          .on_hover_text(&bookmark.anchor)
+          .clicked()
+        {
+          app_event = Some(SubAppEvent::OpenApp(bookmark.anchor.clone()));
+        }
+      });
+    }
+    if let Some(idx) = remove {
+      self.bookmarks.remove(idx);
+      self.editing_bookmark = None;
+      self.need_save = true;
+    }
+    app_event
+  }
+}
@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use egui::*;
+use egui_extras::{Column, TableBuilder};
+
+use super::format::pallet_color;
+use super::{BackendState, FrozenTable, SubApp, SubAppEvent};
+use crate::backend::*;
+
+/// Number of example extrinsics kept per call.
+const CALL_EXAMPLES: usize = 5;
+
+/// How often a call was seen, with its most recent extrinsics.
+#[derive(Default)]
+pub struct CallStats {
+  pub count: u64,
+  /// Most recently loaded first: block number, block hash and extrinsic index.
+  pub examples: VecDeque<(BlockNumber, BlockHash, u32)>,
+}
+
+/// Index of the call names (`Pallet.call`) of the decoded extrinsics, including the calls
+/// nested in batches.
+#[derive(Default)]
+pub struct CallIndex {
+  pub calls: BTreeMap<String, CallStats>,
+  /// Indexed blocks, so reloaded blocks aren't counted twice.
+  blocks: HashSet<BlockHash>,
+}
+
+impl CallIndex {
+  fn add_call(&mut self, call: &CallInfo, example: (BlockNumber, BlockHash, u32)) {
+    let stats = self.calls.entry(call.name.clone()).or_default();
+    stats.count += 1;
+    // Keep one example per extrinsic.
+    if stats.examples.front() != Some(&example) {
+      stats.examples.push_front(example);
+      stats.examples.truncate(CALL_EXAMPLES);
+    }
+    for inner in &call.calls {
+      self.add_call(inner, example);
+    }
+  }
+
+  pub fn add_block(&mut self, block: &BlockInfo) {
+    // Blocks without extrinsics (decoding disabled) aren't marked, so a reload indexes them.
+    if block.extrinsics.is_empty() || !self.blocks.insert(block.hash) {
+      return;
+    }
+    for xt in &block.extrinsics {
+      if let Some(call) = &xt.call {
+        self.add_call(call, (block.number(), block.hash, xt.index));
+      }
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.calls.clear();
+    self.blocks.clear();
+  }
+}
+
+/// Call index sub-app.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct CallIndexApp {
+  /// Only show calls containing this text.
+  filter: String,
+}
+
+/// Links to a call's recent extrinsics, returns the anchor of the clicked one.
+fn examples_ui(ui: &mut Ui, stats: &CallStats) -> Option<String> {
+  let mut anchor = None;
+  for (number, hash, index) in &stats.examples {
+    if ui.link(format!("{number}-{index}")).clicked() {
+      anchor = Some(format!("block_details/{hash:?}/{index}"));
+    }
+  }
+  anchor
+}
+
+impl SubApp for CallIndexApp {
+  fn name(&self) -> &str {
+    "Calls"
+  }
+
+  fn anchor(&self) -> &str {
+    "calls"
+  }
+
+  fn update(
+    &mut self,
+    backend: &mut BackendState,
+    ctx: &egui::Context,
+    _anchor: &str,
+  ) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    egui::CentralPanel::default().show(ctx, |ui| {
+      ui.horizontal(|ui| {
+        ui.label("Filter:");
+        ui.add(
+          TextEdit::singleline(&mut self.filter)
+            .hint_text("e.g. Settlement.add")
+            .desired_width(300.0),
+        );
+        ui.weak("Calls of the extrinsics loaded this session, including batched calls.");
+      });
+      ui.separator();
+      let filter = self.filter.to_lowercase();
+      let mut calls: Vec<_> = backend
+        .call_index
+        .calls
+        .iter()
+        .filter(|(name, _)| name.to_lowercase().contains(&filter))
+        .collect();
+      calls.sort_by(|(a_name, a), (b_name, b)| b.count.cmp(&a.count).then(a_name.cmp(b_name)));
+      let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
+      let frozen = backend.settings.frozen_columns;
+      if frozen > 0 {
+        FrozenTable::new("call_index", frozen, text_height)
+          .column("Call", 300.0)
+          .column("Count", 80.0)
+          .column("Recent extrinsics", 100.0)
+          .show(ui, calls.len(), |ui, row, col| {
+            let (name, stats) = calls[row];
+            match col {
+              0 => {
+                ui.colored_label(pallet_color(name, ui.visuals().dark_mode), name);
+              }
+              1 => {
+                ui.label(stats.count.to_string());
+              }
+              _ => {
+                if let Some(anchor) = examples_ui(ui, stats) {
+                  app_event = Some(SubAppEvent::OpenApp(anchor));
+                }
+              }
+            }
+          });
+        return;
+      }
+      TableBuilder::new(ui)
+        .striped(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(300.0).at_least(100.0))
+        .column(Column::initial(80.0).at_least(40.0))
+        .column(Column::remainder().at_least(100.0))
+        .header(20.0, |mut header| {
+          header.col(|ui| {
+            ui.heading("Call");
+          });
+          header.col(|ui| {
+            ui.heading("Count");
+          });
+          header.col(|ui| {
+            ui.heading("Recent extrinsics");
+          });
+        })
+        .body(|body| {
+          body.rows(text_height, calls.len(), |row_index, mut row| {
+            let (name, stats) = calls[row_index];
+            row.col(|ui| {
+              ui.colored_label(pallet_color(name, ui.visuals().dark_mode), name);
+            });
+            row.col(|ui| {
+              ui.label(stats.count.to_string());
+            });
+            row.col(|ui| {
+              if let Some(anchor) = examples_ui(ui, stats) {
+                app_event = Some(SubAppEvent::OpenApp(anchor));
+              }
+            });
+          });
+        });
+    });
+    app_event
+  }
+
+  fn reset(&mut self) {
+    *self = Default::default();
+  }
+}
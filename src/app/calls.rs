@@ -0,0 +1,372 @@
+use egui::*;
+use serde_json::Value;
+
+use super::format::{format_account, format_balance, pallet_color, AmountFormat};
+use super::json::json_tree_ui;
+use super::settings::Settings;
+use super::staking::value_account;
+use crate::backend::*;
+
+/// Calls that run on behalf of another account: call name prefix, the argument holding the
+/// account and how to label it.
+const ORIGIN_ARGS: &[(&str, &str, &str)] = &[
+  // Polymesh multisig proposals, approvals and rejections.
+  ("MultiSig.", "multisig", "multisig"),
+  ("Proxy.proxy", "real", "proxied"),
+  ("Utility.relay_tx", "target", "relayed for"),
+];
+
+/// Outcome of a call in a batch, from the `Utility` events of its extrinsic.
+#[derive(Clone, Debug, PartialEq)]
+enum ItemStatus {
+  Completed,
+  Failed(String),
+  /// An earlier call interrupted the batch.
+  NotExecuted,
+  /// The extrinsic failed, so the batch was reverted.
+  Reverted,
+  Unknown,
+}
+
+impl ItemStatus {
+  fn ui(&self, ui: &mut Ui) {
+    match self {
+      Self::Completed => {
+        ui.colored_label(Color32::GREEN, "✔");
+      }
+      Self::Failed(err) => {
+        ui.colored_label(ui.visuals().error_fg_color, format!("✖ {err}"));
+      }
+      Self::NotExecuted => {
+        ui.weak("⏹ not executed");
+      }
+      Self::Reverted => {
+        ui.colored_label(ui.visuals().warn_fg_color, "↩ reverted");
+      }
+      Self::Unknown => (),
+    }
+  }
+}
+
+fn event_error(value: &Value) -> String {
+  let error = value.get("error").unwrap_or(value);
+  serde_json::to_string(error).unwrap_or_default()
+}
+
+/// Match the `Utility` item events of an extrinsic to the calls of its batch.
+///
+/// Nested batches emit their own item events, so only batches of plain calls are matched.
+fn batch_statuses(call: &CallInfo, events: &[&EventInfo]) -> Vec<ItemStatus> {
+  let items = call.calls.len();
+  if call.calls.iter().any(|call| !call.calls.is_empty()) {
+    return vec![ItemStatus::Unknown; items];
+  }
+  let mut statuses = Vec::with_capacity(items);
+  for event in events {
+    match event.name.as_ref() {
+      "Utility.ItemCompleted" => statuses.push(ItemStatus::Completed),
+      "Utility.ItemFailed" => statuses.push(ItemStatus::Failed(event_error(&event.value))),
+      "Utility.BatchInterrupted" => {
+        let index = event
+          .value
+          .get("index")
+          .and_then(|index| index.as_u64())
+          .unwrap_or(statuses.len() as u64) as usize;
+        statuses.resize(index, ItemStatus::Unknown);
+        statuses.push(ItemStatus::Failed(event_error(&event.value)));
+        statuses.resize(items, ItemStatus::NotExecuted);
+      }
+      _ => (),
+    }
+  }
+  let failed = events
+    .iter()
+    .any(|event| event.name == "System.ExtrinsicFailed");
+  let missing = if failed {
+    ItemStatus::Reverted
+  } else {
+    ItemStatus::Unknown
+  };
+  statuses.resize(items, missing);
+  statuses
+}
+
+/// Who a call runs on behalf of, when it isn't the signer, e.g. "multisig: 5Grw…".
+fn origin_text(call: &CallInfo, ss58_prefix: u16) -> Option<String> {
+  let origin = ORIGIN_ARGS.iter().find_map(|(prefix, arg, label)| {
+    if !call.name.starts_with(prefix) {
+      return None;
+    }
+    let account = value_account(call.args.get(arg)?)?;
+    Some(format!(
+      "{label}: {}",
+      format_account(&account, ss58_prefix)
+    ))
+  });
+  // Substrate multisig accounts are derived from the signatories, show who they are instead.
+  origin.or_else(|| {
+    if !call.name.starts_with("Multisig.") {
+      return None;
+    }
+    let threshold = call.args.get("threshold")?.as_u64()?;
+    let others = call.args.get("other_signatories")?.as_array()?.len();
+    Some(format!(
+      "multisig: {threshold} of {} signatories",
+      others + 1
+    ))
+  })
+}
+
+/// The first call in the call tree that runs on behalf of another account.
+fn find_origin(call: &CallInfo, ss58_prefix: u16) -> Option<String> {
+  origin_text(call, ss58_prefix).or_else(|| {
+    call
+      .calls
+      .iter()
+      .find_map(|inner| find_origin(inner, ss58_prefix))
+  })
+}
+
+fn call_label(ui: &mut Ui, call: &CallInfo, ss58_prefix: u16) {
+  let color = pallet_color(&call.name, ui.visuals().dark_mode);
+  ui.colored_label(color, &call.name);
+  if let Some(origin) = origin_text(call, ss58_prefix) {
+    ui.weak(format!("({origin})"))
+      .on_hover_text("The call runs on behalf of this account, not the signer");
+  }
+}
+
+/// Call tree, `header_ui` adds the widgets in front of the call name.
+fn call_ui(
+  ui: &mut Ui,
+  id: Id,
+  header_ui: &mut dyn FnMut(&mut Ui),
+  call: &CallInfo,
+  events: &[&EventInfo],
+  status: Option<&ItemStatus>,
+  ss58_prefix: u16,
+) {
+  if call.calls.is_empty() {
+    ui.horizontal(|ui| {
+      header_ui(ui);
+      call_label(ui, call, ss58_prefix);
+      if let Some(status) = status {
+        status.ui(ui);
+      }
+    });
+    return;
+  }
+  let statuses = batch_statuses(call, events);
+  collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, true)
+    .show_header(ui, |ui| {
+      header_ui(ui);
+      call_label(ui, call, ss58_prefix);
+      ui.weak(format!("({} calls)", call.calls.len()));
+      if let Some(status) = status {
+        status.ui(ui);
+      }
+    })
+    .body(|ui| {
+      for (idx, (inner, status)) in call.calls.iter().zip(&statuses).enumerate() {
+        let mut header_ui = |ui: &mut Ui| {
+          ui.label(format!("{idx}:"));
+        };
+        // Nested batches aren't matched to events.
+        call_ui(
+          ui,
+          id.with(idx),
+          &mut header_ui,
+          inner,
+          &[],
+          Some(status),
+          ss58_prefix,
+        );
+      }
+    });
+}
+
+/// Era, nonce and tip of a signed extrinsic, e.g. "mortal, valid blocks 1000–1063 · nonce 5 ·
+/// no tip".
+fn signed_extra_text(
+  block: &BlockInfo,
+  xt: &ExtrinsicInfo,
+  amounts: AmountFormat,
+) -> Option<String> {
+  let era = match xt.era? {
+    Era::Immortal => "immortal".to_string(),
+    era => match era.validity(block.number()) {
+      Some((first, last)) => format!("mortal, valid blocks {first}–{last}"),
+      None => "mortal".to_string(),
+    },
+  };
+  let tip = match xt.tip {
+    Some(tip) if tip > 0 => format!("tip: {}", format_balance(tip, amounts)),
+    _ => "no tip".to_string(),
+  };
+  match xt.nonce {
+    Some(nonce) => Some(format!("{era} · nonce {nonce} · {tip}")),
+    None => Some(format!("{era} · {tip}")),
+  }
+}
+
+fn extrinsic_ui(
+  ui: &mut Ui,
+  block: &BlockInfo,
+  xt: &ExtrinsicInfo,
+  highlight: Option<u32>,
+  ss58_prefix: u16,
+  settings: &Settings,
+  clicked: &mut Option<u32>,
+) {
+  let events = block
+    .events
+    .iter()
+    .filter(|event| event.phase == Phase::ApplyExtrinsic(xt.index))
+    .collect::<Vec<_>>();
+  let mut header_ui = |ui: &mut Ui| {
+    let label = RichText::new(format!("#{}", xt.index));
+    let label = if Some(xt.index) == highlight {
+      label.strong().color(ui.visuals().warn_fg_color)
+    } else {
+      label
+    };
+    if ui
+      .link(label)
+      .on_hover_text("Highlight the extrinsic's events")
+      .clicked()
+    {
+      *clicked = Some(xt.index);
+    }
+  };
+  match &xt.call {
+    Some(call) => {
+      let id = ui.id().with(xt.index);
+      call_ui(ui, id, &mut header_ui, call, &events, None, ss58_prefix);
+    }
+    None => {
+      ui.horizontal(|ui| {
+        header_ui(ui);
+        ui.colored_label(ui.visuals().warn_fg_color, "Failed to decode call");
+      });
+    }
+  }
+  // Show the signer next to the account the call runs on behalf of.
+  let origin = xt.signer.zip(xt.call.as_ref()).and_then(|(signer, call)| {
+    let origin = find_origin(call, ss58_prefix)?;
+    Some(format!(
+      "signed by {} · {origin}",
+      format_account(&signer, ss58_prefix)
+    ))
+  });
+  let extra = signed_extra_text(block, xt, settings.amount_format);
+  ui.indent(("signed_extra", xt.index), |ui| {
+    if let Some(origin) = origin {
+      ui.weak(origin);
+    }
+    if let Some(extra) = extra {
+      ui.weak(extra);
+    }
+    args_ui(ui, block, xt, settings);
+  });
+}
+
+/// Collapsed call arguments of an extrinsic, or its raw bytes if the call couldn't be decoded.
+fn args_ui(ui: &mut Ui, block: &BlockInfo, xt: &ExtrinsicInfo, settings: &Settings) {
+  match (&xt.call, &xt.raw) {
+    (Some(call), _) if !call.args.is_null() => {
+      CollapsingHeader::new("Arguments")
+        .id_source(("call_args", xt.index))
+        .show(ui, |ui| {
+          json_tree_ui(
+            ui,
+            ("call_args", block.hash, xt.index),
+            "args",
+            &call.args,
+            settings.large_json_nodes,
+            settings.json_expand_depth,
+          );
+        });
+    }
+    (None, Some(raw)) => {
+      CollapsingHeader::new("Raw bytes")
+        .id_source(("call_raw", xt.index))
+        .show(ui, |ui| {
+          ui.add(Label::new(RichText::new(raw).monospace()).wrap(true));
+        });
+    }
+    _ => (),
+  }
+}
+
+/// Extrinsics grouped by signer, in order of each signer's first extrinsic.  Unsigned
+/// extrinsics are grouped under `None`.
+fn group_by_signer(extrinsics: &[ExtrinsicInfo]) -> Vec<(Option<AccountKey>, Vec<&ExtrinsicInfo>)> {
+  let mut groups: Vec<(Option<AccountKey>, Vec<&ExtrinsicInfo>)> = Vec::new();
+  for xt in extrinsics {
+    match groups.iter_mut().find(|(signer, _)| *signer == xt.signer) {
+      Some((_, group)) => group.push(xt),
+      None => groups.push((xt.signer, vec![xt])),
+    }
+  }
+  groups
+}
+
+/// Decoded calls of a block's extrinsics, with the calls nested in batches and proxies.
+///
+/// Returns the extrinsic the user clicked on.
+pub fn block_calls_ui(
+  ui: &mut Ui,
+  block: &BlockInfo,
+  highlight: Option<u32>,
+  by_signer: &mut bool,
+  ss58_prefix: u16,
+  settings: &Settings,
+) -> Option<u32> {
+  let mut clicked = None;
+  CollapsingHeader::new(format!("Calls ({} extrinsics)", block.extrinsics.len()))
+    .id_source("Calls")
+    .show(ui, |ui| {
+      ui.checkbox(by_signer, "Group by signer");
+      ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+        if !*by_signer {
+          for xt in &block.extrinsics {
+            extrinsic_ui(
+              ui,
+              block,
+              xt,
+              highlight,
+              ss58_prefix,
+              settings,
+              &mut clicked,
+            );
+          }
+          return;
+        }
+        for (signer, group) in group_by_signer(&block.extrinsics) {
+          let signer = match signer {
+            Some(signer) => format_account(&signer, ss58_prefix),
+            None => "Unsigned".to_string(),
+          };
+          ui.separator();
+          ui.horizontal(|ui| {
+            ui.strong(signer);
+            ui.weak(format!("({} extrinsics)", group.len()));
+          });
+          ui.indent(("signer_group", group[0].index), |ui| {
+            for xt in group {
+              extrinsic_ui(
+                ui,
+                block,
+                xt,
+                highlight,
+                ss58_prefix,
+                settings,
+                &mut clicked,
+              );
+            }
+          });
+        }
+      });
+    });
+  clicked
+}
@@ -0,0 +1,164 @@
+use egui::*;
+use serde_json::Value;
+
+use super::format::{format_balance, AmountFormat};
+use super::json::json_tree_ui;
+use super::{BackendState, SubApp, SubAppEvent};
+use crate::backend::*;
+
+/// Words in the names of `u128` constants that are POLYX amounts.
+const BALANCE_WORDS: &[&str] = &["Deposit", "Fee", "Balance", "Bond", "Reward", "Amount"];
+
+/// Pallet constants sub-app.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ConstantsApp {
+  /// Only show constants with the pallet or constant name containing this text.
+  filter: String,
+}
+
+/// The constant as a POLYX amount, if it looks like one.
+fn constant_balance(constant: &ConstantInfo, amounts: AmountFormat) -> Option<String> {
+  if constant.type_name != "u128" || !BALANCE_WORDS.iter().any(|w| constant.name.contains(w)) {
+    return None;
+  }
+  let amount = match &constant.value {
+    Value::Number(n) => n.as_u64()? as u128,
+    Value::String(n) => n.parse().ok()?,
+    _ => return None,
+  };
+  Some(format_balance(amount, amounts))
+}
+
+fn constant_ui(ui: &mut Ui, backend: &BackendState, pallet: &str, constant: &ConstantInfo) {
+  ui.horizontal(|ui| {
+    ui.strong(&constant.name);
+    ui.weak(&constant.type_name);
+    match (
+      constant_balance(constant, backend.settings.amount_format),
+      &constant.value,
+    ) {
+      (Some(balance), value) => {
+        ui.label(balance).on_hover_text(value.to_string());
+      }
+      (None, value @ (Value::Number(_) | Value::String(_) | Value::Bool(_))) => {
+        ui.monospace(value.to_string());
+      }
+      (None, _) => (),
+    }
+  });
+  if matches!(constant.value, Value::Array(_) | Value::Object(_)) {
+    json_tree_ui(
+      ui,
+      (pallet, &constant.name),
+      "value",
+      &constant.value,
+      backend.settings.large_json_nodes,
+      backend.settings.json_expand_depth,
+    );
+  }
+  if !constant.docs.is_empty() {
+    ui.weak(&constant.docs);
+  }
+  ui.add_space(4.0);
+}
+
+impl SubApp for ConstantsApp {
+  fn name(&self) -> &str {
+    "Constants"
+  }
+
+  fn anchor(&self) -> &str {
+    "constants"
+  }
+
+  fn update(
+    &mut self,
+    backend: &mut BackendState,
+    ctx: &egui::Context,
+    _anchor: &str,
+  ) -> Option<SubAppEvent> {
+    egui::CentralPanel::default().show(ctx, |ui| {
+      ui.horizontal(|ui| {
+        ui.label("Filter:");
+        ui.add(
+          TextEdit::singleline(&mut self.filter)
+            .hint_text("e.g. ExistentialDeposit")
+            .desired_width(300.0),
+        );
+        ui.weak("Pallet constants of the current runtime.");
+        if ui.button("⟳ Reload").clicked() {
+          backend.constants = None;
+          backend.get_constants();
+        }
+        let loading = matches!(backend.metadata_export, Some(None));
+        if ui
+          .add_enabled(!loading, Button::new("⬇ Download metadata"))
+          .on_hover_text(
+            "Save the SCALE encoded runtime metadata as metadata.scale, for other tools",
+          )
+          .clicked()
+        {
+          backend.export_metadata();
+        }
+        match &backend.metadata_export {
+          Some(None) => {
+            ui.spinner();
+          }
+          Some(Some(Ok(saved))) => {
+            ui.weak(saved);
+          }
+          Some(Some(Err(err))) => {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+          }
+          None => (),
+        }
+      });
+      ui.separator();
+      let pallets = match &backend.constants {
+        Some(Ok(pallets)) => pallets,
+        Some(Err(err)) => {
+          ui.colored_label(
+            ui.visuals().error_fg_color,
+            format!("Failed to load constants: {err}"),
+          );
+          return;
+        }
+        None => {
+          ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("Loading constants...");
+          });
+          return;
+        }
+      };
+      let filter = self.filter.to_lowercase();
+      ScrollArea::vertical().show(ui, |ui| {
+        for pallet in pallets {
+          let pallet_matches = pallet.pallet.to_lowercase().contains(&filter);
+          let constants: Vec<_> = pallet
+            .constants
+            .iter()
+            .filter(|c| pallet_matches || c.name.to_lowercase().contains(&filter))
+            .collect();
+          if constants.is_empty() {
+            continue;
+          }
+          CollapsingHeader::new(format!("{} ({})", pallet.pallet, constants.len()))
+            .id_source(&pallet.pallet)
+            .open(if filter.is_empty() { None } else { Some(true) })
+            .show(ui, |ui| {
+              for constant in constants {
+                constant_ui(ui, backend, &pallet.pallet, constant);
+              }
+            });
+        }
+      });
+    });
+    None
+  }
+
+  fn reset(&mut self) {
+    *self = Default::default();
+  }
+}
@@ -0,0 +1,500 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use egui::*;
+
+use super::format::{format_balance, format_duration, pallet_color};
+use super::{BackendState, SubApp, SubAppEvent};
+use crate::backend::*;
+
+/// Number of recent blocks used for the dashboard averages and sparkline.
+const DASHBOARD_BLOCKS: u32 = 60;
+
+/// Size of a heat map cell.
+const HEAT_MAP_CELL: f32 = 12.0;
+
+/// Largest window compared, in blocks.  Both windows have to fit in the block cache.
+const MAX_COMPARE_BLOCKS: u32 = 500;
+
+/// Recent cached blocks, newest first.
+fn recent_blocks(backend: &BackendState) -> impl Iterator<Item = &BlockInfo> {
+  let best = backend.best_block;
+  (0..DASHBOARD_BLOCKS)
+    .filter_map(move |offset| best.checked_sub(offset))
+    .filter_map(|number| backend.blocks.get(&number))
+}
+
+/// Average block time in seconds, from the block timestamps.
+fn average_block_time(backend: &BackendState) -> Option<f64> {
+  let mut blocks = recent_blocks(backend).filter(|block| block.timestamp.is_some());
+  let newest = blocks.next()?;
+  let oldest = blocks.last()?;
+  let count = newest.number() - oldest.number();
+  let elapsed = newest.timestamp? - oldest.timestamp?;
+  Some(elapsed as f64 / count as f64 / 1000.0)
+}
+
+fn average_events(backend: &BackendState) -> Option<f64> {
+  let (blocks, events) = recent_blocks(backend).fold((0, 0), |(blocks, events), block| {
+    (blocks + 1, events + block.event_count())
+  });
+  (blocks > 0).then(|| events as f64 / blocks as f64)
+}
+
+/// Color between `low` and `high`, `t` in `0.0..=1.0`.
+fn heat_color(low: Color32, high: Color32, t: f32) -> Color32 {
+  let mix = |a: u8, b: u8| lerp(a as f32..=b as f32, t).round() as u8;
+  Color32::from_rgb(
+    mix(low.r(), high.r()),
+    mix(low.g(), high.g()),
+    mix(low.b(), high.b()),
+  )
+}
+
+/// Clickable dashboard tile.
+fn tile(ui: &mut Ui, title: &str, value: String) -> Response {
+  let resp = egui::Frame::group(ui.style())
+    .show(ui, |ui| {
+      ui.set_min_size(vec2(180.0, 60.0));
+      ui.vertical(|ui| {
+        ui.weak(title);
+        ui.label(RichText::new(value).heading());
+      });
+    })
+    .response;
+  ui.interact(resp.rect, ui.id().with(title), Sense::click())
+    .on_hover_cursor(CursorIcon::PointingHand)
+}
+
+/// Dashboard tile with a needle gauge, for the block arrival rate.  The scale grows in powers of
+/// ten, so the needle stays readable while preloading.
+fn rate_tile(ui: &mut Ui, title: &str, rate: Option<f64>) -> Response {
+  let resp = egui::Frame::group(ui.style())
+    .show(ui, |ui| {
+      ui.set_min_size(vec2(180.0, 60.0));
+      ui.horizontal(|ui| {
+        ui.vertical(|ui| {
+          ui.weak(title);
+          let value = rate.map_or("-".into(), |rate| format!("{rate:.2}"));
+          ui.label(RichText::new(value).heading());
+        });
+        let rate = rate.unwrap_or(0.0);
+        let mut scale = 1.0;
+        while rate > scale {
+          scale *= 10.0;
+        }
+        let (rect, _) = ui.allocate_exact_size(vec2(60.0, 36.0), Sense::hover());
+        let painter = ui.painter_at(rect.expand(2.0));
+        let center = rect.center_bottom();
+        let radius = rect.height().min(rect.width() / 2.0);
+        // Half circle from the left (0) to the right (`scale`).
+        let point = |t: f64, len: f32| {
+          let angle = std::f32::consts::PI * (1.0 - t.clamp(0.0, 1.0) as f32);
+          center + len * vec2(angle.cos(), -angle.sin())
+        };
+        let arc: Vec<_> = (0..=20).map(|i| point(i as f64 / 20.0, radius)).collect();
+        painter.add(Shape::line(
+          arc,
+          ui.visuals().widgets.noninteractive.fg_stroke,
+        ));
+        let needle = Stroke::new(2.0, ui.visuals().selection.bg_fill);
+        painter.line_segment([center, point(rate / scale, radius - 2.0)], needle);
+        painter.text(
+          rect.right_bottom(),
+          Align2::RIGHT_BOTTOM,
+          format!("{scale}"),
+          FontId::proportional(9.0),
+          ui.visuals().weak_text_color(),
+        );
+      });
+    })
+    .response;
+  ui.interact(resp.rect, ui.id().with(title), Sense::click())
+    .on_hover_cursor(CursorIcon::PointingHand)
+}
+
+/// Range of blocks compared by the dashboard.
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+struct BlockWindow {
+  start: BlockNumber,
+  len: u32,
+}
+
+impl Default for BlockWindow {
+  fn default() -> Self {
+    Self { start: 0, len: 100 }
+  }
+}
+
+impl BlockWindow {
+  fn numbers(&self) -> std::ops::Range<BlockNumber> {
+    self.start..self.start.saturating_add(self.len)
+  }
+
+  /// Per-pallet event counts of the window's cached blocks, and the number of cached blocks.
+  fn pallet_counts(&self, backend: &BackendState) -> (BTreeMap<String, usize>, u32) {
+    let mut counts = BTreeMap::new();
+    let mut cached = 0;
+    for block in self
+      .numbers()
+      .filter_map(|number| backend.blocks.get(&number))
+    {
+      cached += 1;
+      for (pallet, count) in block.pallet_event_counts() {
+        *counts.entry(pallet.to_string()).or_default() += count;
+      }
+    }
+    (counts, cached)
+  }
+
+  /// Request the window's blocks that aren't cached, one range per gap.
+  fn load_missing(&self, backend: &BackendState) {
+    let mut gap: Option<(BlockNumber, u32)> = None;
+    for number in self
+      .numbers()
+      .filter(|number| *number <= backend.best_block)
+    {
+      if backend.blocks.contains_key(&number) {
+        if let Some((start, count)) = gap.take() {
+          backend.get_block_range(start, count);
+        }
+      } else {
+        let (_, count) = gap.get_or_insert((number, 0));
+        *count += 1;
+      }
+    }
+    if let Some((start, count)) = gap {
+      backend.get_block_range(start, count);
+    }
+  }
+
+  fn ui(&mut self, ui: &mut Ui, label: &str) {
+    ui.label(label);
+    ui.label("from #");
+    ui.add(DragValue::new(&mut self.start));
+    ui.label("blocks:");
+    ui.add(DragValue::new(&mut self.len).clamp_range(1..=MAX_COMPARE_BLOCKS));
+  }
+}
+
+/// Chain at a glance sub-app.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DashboardApp {
+  /// Earlier window of the event count comparison.
+  compare_a: BlockWindow,
+  /// Later window of the event count comparison.
+  compare_b: BlockWindow,
+}
+
+impl DashboardApp {
+  fn tiles_ui(&mut self, backend: &BackendState, ui: &mut Ui) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    let explorer = || Some(SubAppEvent::OpenApp("explorer".into()));
+    ui.horizontal_wrapped(|ui| {
+      let best = backend.blocks.get(&backend.best_block);
+      let value = best.map_or("-".into(), |block| format!("#{}", block.number()));
+      if tile(ui, "Best block", value).clicked() {
+        if let Some(block) = best {
+          app_event = Some(SubAppEvent::BlockDetails(block.hash));
+        }
+      }
+      let status = backend.chain_status.as_ref();
+      let finalized = status.and_then(|status| status.finalized);
+      let value = finalized.map_or("-".into(), |(number, _)| format!("#{number}"));
+      if tile(ui, "Finalized block", value).clicked() {
+        if let Some((_, hash)) = finalized {
+          app_event = Some(SubAppEvent::BlockDetails(hash));
+        }
+      }
+      let lag = finalized.map(|(number, _)| backend.best_block.saturating_sub(number));
+      let value = lag.map_or("-".into(), |lag| format!("{lag} blocks"));
+      if tile(ui, "Finality lag", value)
+        .on_hover_text("Blocks between the best and the finalized block")
+        .clicked()
+      {
+        app_event = explorer();
+      }
+      if tile(ui, "Connection", backend.status.to_string()).clicked() {
+        app_event = explorer();
+      }
+      let value = status.map_or("-".into(), |status| format!("{} ms", status.latency_ms));
+      if tile(ui, "Latency", value)
+        .on_hover_text("Round trip time of a request to the node")
+        .clicked()
+      {
+        app_event = explorer();
+      }
+      let value = status
+        .and_then(|status| status.total_issuance)
+        .map_or("-".into(), |amount| {
+          format_balance(amount, backend.settings.amount_format)
+        });
+      if tile(ui, "Total issuance", value).clicked() {
+        app_event = explorer();
+      }
+      let value = status
+        .and_then(|status| status.active_era)
+        .map_or("-".into(), |era| format!("#{era}"));
+      if tile(ui, "Active era", value).clicked() {
+        app_event = explorer();
+      }
+      let value = average_block_time(backend).map_or("-".into(), |secs| format!("{secs:.2}s"));
+      if tile(ui, "Block time", value).clicked() {
+        app_event = explorer();
+      }
+      if rate_tile(ui, "Blocks / s", backend.block_rate.rate())
+        .on_hover_text("Blocks loaded per second, including preloaded blocks")
+        .clicked()
+      {
+        app_event = explorer();
+      }
+      let value = average_events(backend).map_or("-".into(), |events| format!("{events:.1}"));
+      if tile(ui, "Events / block", value).clicked() {
+        app_event = explorer();
+      }
+      let value = format!(
+        "{} ({} reconnects)",
+        format_duration(backend.stats.uptime()),
+        backend.stats.reconnects
+      );
+      if tile(ui, "Session", value).clicked() {
+        app_event = explorer();
+      }
+    });
+    app_event
+  }
+
+  /// Events per block for the recent blocks.  Click a bar to open the block.
+  fn sparkline_ui(&mut self, backend: &BackendState, ui: &mut Ui) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    ui.label("Recent activity (events per block):");
+    let size = vec2(ui.available_width(), 80.0);
+    let (rect, resp) = ui.allocate_exact_size(size, Sense::click());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let blocks: Vec<_> = recent_blocks(backend).collect();
+    let max = blocks
+      .iter()
+      .map(|b| b.event_count())
+      .max()
+      .unwrap_or(0)
+      .max(1);
+    let bar_width = rect.width() / DASHBOARD_BLOCKS as f32;
+    let color = ui.visuals().selection.bg_fill;
+    for block in &blocks {
+      // Newest block on the right.
+      let offset = backend.best_block - block.number();
+      let x = rect.right() - (offset + 1) as f32 * bar_width;
+      let height = rect.height() * block.event_count() as f32 / max as f32;
+      let bar = Rect::from_min_max(
+        pos2(x + 1.0, rect.bottom() - height),
+        pos2(x + bar_width - 1.0, rect.bottom()),
+      );
+      painter.rect_filled(bar, 0.0, color);
+    }
+
+    // Find the block under the pointer.
+    let hovered = resp.hover_pos().and_then(|pos| {
+      let offset = ((rect.right() - pos.x) / bar_width) as u32;
+      backend
+        .best_block
+        .checked_sub(offset)
+        .and_then(|number| backend.blocks.get(&number))
+    });
+    if let Some(block) = hovered {
+      let resp = resp.on_hover_text(format!(
+        "#{}: {} events",
+        block.number(),
+        block.event_count()
+      ));
+      if resp.clicked() {
+        app_event = Some(SubAppEvent::BlockDetails(block.hash));
+      }
+    }
+    app_event
+  }
+
+  /// Per-pallet event counts of two block windows, with the change from window A to B.
+  fn compare_ui(&mut self, backend: &BackendState, ui: &mut Ui) {
+    ui.horizontal_wrapped(|ui| {
+      self.compare_a.ui(ui, "Window A");
+      ui.separator();
+      self.compare_b.ui(ui, "Window B");
+      if ui
+        .button("Latest")
+        .on_hover_text("Compare the latest blocks with the same number of blocks before them")
+        .clicked()
+      {
+        let len = self.compare_b.len;
+        self.compare_b.start = backend.best_block.saturating_sub(len - 1);
+        self.compare_a = BlockWindow {
+          start: self.compare_b.start.saturating_sub(len),
+          len,
+        };
+      }
+    });
+    let (counts_a, cached_a) = self.compare_a.pallet_counts(backend);
+    let (counts_b, cached_b) = self.compare_b.pallet_counts(backend);
+    ui.horizontal(|ui| {
+      ui.weak(format!(
+        "Cached blocks: A {cached_a}/{}, B {cached_b}/{}.",
+        self.compare_a.len, self.compare_b.len
+      ));
+      if (cached_a < self.compare_a.len || cached_b < self.compare_b.len)
+        && ui.button("Load missing blocks").clicked()
+      {
+        self.compare_a.load_missing(backend);
+        self.compare_b.load_missing(backend);
+      }
+    });
+    if cached_a == 0 || cached_b == 0 {
+      return;
+    }
+    // Compare events per cached block, so missing blocks don't skew the change.
+    let rate = |count: usize, cached: u32| count as f64 / cached as f64;
+    let mut rows: Vec<_> = counts_a
+      .keys()
+      .chain(counts_b.keys())
+      .collect::<BTreeSet<_>>()
+      .into_iter()
+      .map(|pallet| {
+        let a = counts_a.get(pallet).copied().unwrap_or(0);
+        let b = counts_b.get(pallet).copied().unwrap_or(0);
+        (pallet, a, b, rate(b, cached_b) - rate(a, cached_a))
+      })
+      .collect();
+    rows.sort_by(|x, y| y.3.abs().total_cmp(&x.3.abs()));
+    Grid::new("compare_windows")
+      .num_columns(4)
+      .striped(true)
+      .show(ui, |ui| {
+        ui.strong("Pallet");
+        ui.strong("A");
+        ui.strong("B");
+        ui.strong("Change (per block)");
+        ui.end_row();
+        for (pallet, a, b, delta) in rows {
+          ui.colored_label(pallet_color(pallet, ui.visuals().dark_mode), pallet);
+          ui.label(a.to_string());
+          ui.label(b.to_string());
+          let change = if a == 0 {
+            "new".to_string()
+          } else {
+            format!("{:+.0}%", delta / rate(a, cached_a) * 100.0)
+          };
+          let color = if delta > 0.0 {
+            Color32::GREEN
+          } else if delta < 0.0 {
+            ui.visuals().error_fg_color
+          } else {
+            ui.visuals().text_color()
+          };
+          ui.colored_label(color, change);
+          ui.end_row();
+        }
+      });
+  }
+
+  /// Event counts of all cached blocks, one cell per block, newest first.  Click a cell to open
+  /// the block.
+  fn heat_map_ui(&mut self, backend: &BackendState, ui: &mut Ui) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    ui.label("Event activity (cached blocks):");
+    let blocks: Vec<_> = backend
+      .recent_blocks()
+      .filter_map(|number| backend.block(number))
+      .collect();
+    if blocks.is_empty() {
+      return None;
+    }
+    let max = blocks
+      .iter()
+      .map(|b| b.event_count())
+      .max()
+      .unwrap_or(0)
+      .max(1);
+    let per_row = ((ui.available_width() / HEAT_MAP_CELL) as usize).max(1);
+    let rows = blocks.chunks(per_row).len();
+    let size = vec2(per_row as f32, rows as f32) * HEAT_MAP_CELL;
+    let (rect, resp) = ui.allocate_exact_size(size, Sense::click());
+    let painter = ui.painter_at(rect);
+    let low = ui.visuals().extreme_bg_color;
+    let high = ui.visuals().selection.bg_fill;
+    let cell_rect = |idx: usize| {
+      let min = rect.min + vec2((idx % per_row) as f32, (idx / per_row) as f32) * HEAT_MAP_CELL;
+      Rect::from_min_size(min, Vec2::splat(HEAT_MAP_CELL)).shrink(1.0)
+    };
+    for (idx, block) in blocks.iter().enumerate() {
+      let t = block.event_count() as f32 / max as f32;
+      painter.rect_filled(cell_rect(idx), 1.0, heat_color(low, high, t));
+    }
+
+    // Find the block under the pointer.
+    let hovered = resp.hover_pos().and_then(|pos| {
+      let offset = (pos - rect.min) / HEAT_MAP_CELL;
+      let (col, row) = (offset.x as usize, offset.y as usize);
+      if col >= per_row {
+        return None;
+      }
+      blocks.get(row * per_row + col)
+    });
+    if let Some(block) = hovered {
+      let resp = resp.on_hover_text(format!(
+        "#{}: {} events",
+        block.number(),
+        block.event_count()
+      ));
+      if resp.clicked() {
+        app_event = Some(SubAppEvent::BlockDetails(block.hash));
+      }
+    }
+    app_event
+  }
+}
+
+impl SubApp for DashboardApp {
+  fn name(&self) -> &str {
+    "Dashboard"
+  }
+
+  fn anchor(&self) -> &str {
+    "dashboard"
+  }
+
+  fn update(
+    &mut self,
+    backend: &mut BackendState,
+    ctx: &egui::Context,
+    _anchor: &str,
+  ) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    // Keep the compared blocks cached, when older blocks are trimmed.
+    backend.keep_blocks(self.compare_a.numbers());
+    backend.keep_blocks(self.compare_b.numbers());
+    backend.refresh_chain_status(ctx.input(|i| i.time));
+    egui::CentralPanel::default().show(ctx, |ui| {
+      if let Some(event) = self.tiles_ui(backend, ui) {
+        app_event = Some(event);
+      }
+      ui.separator();
+      if let Some(event) = self.sparkline_ui(backend, ui) {
+        app_event = Some(event);
+      }
+      ui.separator();
+      ScrollArea::vertical().show(ui, |ui| {
+        CollapsingHeader::new("Compare windows").show(ui, |ui| {
+          self.compare_ui(backend, ui);
+        });
+        if let Some(event) = self.heat_map_ui(backend, ui) {
+          app_event = Some(event);
+        }
+      });
+    });
+    app_event
+  }
+
+  fn reset(&mut self) {
+    *self = Default::default();
+  }
+}
@@ -0,0 +1,35 @@
+//! Saving files: native builds write them to the working directory, the web downloads them.
+
+/// Save `bytes` as `name`.  Returns where the file went, for showing the user.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(name: &str, bytes: &[u8]) -> Result<String, String> {
+  std::fs::write(name, bytes).map_err(|e| e.to_string())?;
+  let path = std::fs::canonicalize(name).map_err(|e| e.to_string())?;
+  Ok(format!("Saved to {}", path.display()))
+}
+
+/// Save `bytes` as `name`.  Returns where the file went, for showing the user.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(name: &str, bytes: &[u8]) -> Result<String, String> {
+  use js_sys::{Array, Uint8Array};
+  use wasm_bindgen::{JsCast, JsValue};
+
+  let js_err = |e: JsValue| format!("{e:?}");
+  let parts = Array::of1(&Uint8Array::from(bytes));
+  let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(js_err)?;
+  let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(js_err)?;
+  let document = web_sys::window()
+    .and_then(|window| window.document())
+    .ok_or("No document")?;
+  // A temporary link with the `download` attribute makes the browser save the blob.
+  let link = document
+    .create_element("a")
+    .map_err(js_err)?
+    .dyn_into::<web_sys::HtmlAnchorElement>()
+    .map_err(|_| "Not a link element")?;
+  link.set_href(&url);
+  link.set_download(name);
+  link.click();
+  web_sys::Url::revoke_object_url(&url).map_err(js_err)?;
+  Ok(format!("Downloaded {name}"))
+}
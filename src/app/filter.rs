@@ -0,0 +1,490 @@
+use std::borrow::Cow;
+
+use serde_json::Value;
+
+use crate::backend::BlockNumber;
+
+/// Syntax help shown next to the filter.
+pub const FILTER_HELP: &str = "Filter expression, e.g. name startsWith \"Asset.\" and count > 1\n\
+  Fields: name, pallet, block, index, count, value (value.field, value[0])\n\
+  Comparisons: == != > >= < <= contains startsWith endsWith\n\
+  Combine with and, or, not and parentheses.  A field alone checks that it is set.";
+
+/// Event fields an expression can look at.
+pub struct FilterEvent<'a> {
+  pub name: &'a str,
+  pub block: BlockNumber,
+  pub index: u32,
+  /// Events of the same type in the block.
+  pub count: u32,
+  /// `None` if the block isn't cached.
+  pub value: Option<&'a Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+  Ident(String),
+  Str(String),
+  Num(f64),
+  Symbol(&'static str),
+}
+
+const SYMBOLS: &[&str] = &[
+  "==", "!=", ">=", "<=", "&&", "||", ">", "<", "!", "(", ")", ".", "[", "]",
+];
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+  let mut tokens = Vec::new();
+  let mut chars = input.char_indices().peekable();
+  while let Some(&(pos, c)) = chars.peek() {
+    if c.is_whitespace() {
+      chars.next();
+    } else if c == '"' {
+      chars.next();
+      let mut text = String::new();
+      loop {
+        match chars.next() {
+          Some((_, '"')) => break,
+          Some((_, '\\')) => match chars.next() {
+            Some((_, c)) => text.push(c),
+            None => return Err("Unterminated string".into()),
+          },
+          Some((_, c)) => text.push(c),
+          None => return Err("Unterminated string".into()),
+        }
+      }
+      tokens.push(Token::Str(text));
+    } else if c.is_ascii_digit() {
+      let mut end = pos;
+      while let Some(&(idx, c)) = chars.peek() {
+        if !c.is_ascii_digit() && c != '.' {
+          break;
+        }
+        end = idx + c.len_utf8();
+        chars.next();
+      }
+      let num = input[pos..end]
+        .parse()
+        .map_err(|_| format!("Invalid number: {}", &input[pos..end]))?;
+      tokens.push(Token::Num(num));
+    } else if c.is_alphabetic() || c == '_' {
+      let mut end = pos;
+      while let Some(&(idx, c)) = chars.peek() {
+        if !c.is_alphanumeric() && c != '_' {
+          break;
+        }
+        end = idx + c.len_utf8();
+        chars.next();
+      }
+      tokens.push(Token::Ident(input[pos..end].to_string()));
+    } else {
+      let symbol = SYMBOLS
+        .iter()
+        .find(|symbol| input[pos..].starts_with(**symbol))
+        .ok_or_else(|| format!("Unexpected {c:?}"))?;
+      for _ in 0..symbol.len() {
+        chars.next();
+      }
+      tokens.push(Token::Symbol(symbol));
+    }
+  }
+  Ok(tokens)
+}
+
+#[derive(Clone, Debug)]
+enum PathPart {
+  Key(String),
+  Index(usize),
+}
+
+#[derive(Clone, Debug)]
+enum Field {
+  Name,
+  Pallet,
+  Block,
+  Index,
+  Count,
+  Value(Vec<PathPart>),
+}
+
+#[derive(Clone, Debug)]
+enum Operand {
+  Field(Field),
+  Literal(Value),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+  Eq,
+  Ne,
+  Gt,
+  Ge,
+  Lt,
+  Le,
+  Contains,
+  StartsWith,
+  EndsWith,
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+  And(Box<Expr>, Box<Expr>),
+  Or(Box<Expr>, Box<Expr>),
+  Not(Box<Expr>),
+  Compare(Operand, Op, Operand),
+  /// The field is set, e.g. `value.ticker`.
+  IsSet(Field),
+}
+
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.pos).cloned();
+    self.pos += 1;
+    token
+  }
+
+  /// Consume the next token if it is one of `keywords` or `symbols`.
+  fn eat(&mut self, keywords: &[&str], symbols: &[&str]) -> bool {
+    let found = match self.peek() {
+      Some(Token::Ident(ident)) => keywords.iter().any(|k| ident.eq_ignore_ascii_case(k)),
+      Some(Token::Symbol(symbol)) => symbols.contains(symbol),
+      _ => false,
+    };
+    if found {
+      self.pos += 1;
+    }
+    found
+  }
+
+  fn expect(&mut self, symbol: &str) -> Result<(), String> {
+    match self.next() {
+      Some(Token::Symbol(found)) if found == symbol => Ok(()),
+      Some(token) => Err(format!("Expected `{symbol}`, found {token:?}")),
+      None => Err(format!("Expected `{symbol}`")),
+    }
+  }
+
+  fn or(&mut self) -> Result<Expr, String> {
+    let mut expr = self.and()?;
+    while self.eat(&["or"], &["||"]) {
+      expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+    }
+    Ok(expr)
+  }
+
+  fn and(&mut self) -> Result<Expr, String> {
+    let mut expr = self.not()?;
+    while self.eat(&["and"], &["&&"]) {
+      expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+    }
+    Ok(expr)
+  }
+
+  fn not(&mut self) -> Result<Expr, String> {
+    if self.eat(&["not"], &["!"]) {
+      return Ok(Expr::Not(Box::new(self.not()?)));
+    }
+    if self.eat(&[], &["("]) {
+      let expr = self.or()?;
+      self.expect(")")?;
+      return Ok(expr);
+    }
+    self.compare()
+  }
+
+  fn compare(&mut self) -> Result<Expr, String> {
+    let left = self.operand()?;
+    let op = match self.peek() {
+      Some(Token::Symbol(symbol)) => match *symbol {
+        "==" => Some(Op::Eq),
+        "!=" => Some(Op::Ne),
+        ">" => Some(Op::Gt),
+        ">=" => Some(Op::Ge),
+        "<" => Some(Op::Lt),
+        "<=" => Some(Op::Le),
+        _ => None,
+      },
+      Some(Token::Ident(ident)) => match ident.to_ascii_lowercase().as_str() {
+        "contains" => Some(Op::Contains),
+        "startswith" => Some(Op::StartsWith),
+        "endswith" => Some(Op::EndsWith),
+        _ => None,
+      },
+      _ => None,
+    };
+    let Some(op) = op else {
+      return match left {
+        Operand::Field(field) => Ok(Expr::IsSet(field)),
+        Operand::Literal(value) => Err(format!("Expected a comparison after {value}")),
+      };
+    };
+    self.pos += 1;
+    Ok(Expr::Compare(left, op, self.operand()?))
+  }
+
+  fn operand(&mut self) -> Result<Operand, String> {
+    let ident = match self.next() {
+      Some(Token::Str(text)) => return Ok(Operand::Literal(Value::String(text))),
+      Some(Token::Num(num)) => return Ok(Operand::Literal(num.into())),
+      Some(Token::Ident(ident)) => ident,
+      Some(token) => return Err(format!("Expected a field or value, found {token:?}")),
+      None => return Err("Expected a field or value".into()),
+    };
+    let field = match ident.to_ascii_lowercase().as_str() {
+      "name" => Field::Name,
+      "pallet" => Field::Pallet,
+      "block" => Field::Block,
+      "index" => Field::Index,
+      "count" => Field::Count,
+      "value" => Field::Value(self.path()?),
+      "true" => return Ok(Operand::Literal(true.into())),
+      "false" => return Ok(Operand::Literal(false.into())),
+      _ => return Err(format!("Unknown field `{ident}`")),
+    };
+    Ok(Operand::Field(field))
+  }
+
+  /// Path into an event value, e.g. `.ticker[0]`.
+  fn path(&mut self) -> Result<Vec<PathPart>, String> {
+    let mut path = Vec::new();
+    loop {
+      if self.eat(&[], &["."]) {
+        match self.next() {
+          Some(Token::Ident(key)) => path.push(PathPart::Key(key)),
+          Some(Token::Str(key)) => path.push(PathPart::Key(key)),
+          _ => return Err("Expected a key after `.`".into()),
+        }
+      } else if self.eat(&[], &["["]) {
+        match self.next() {
+          Some(Token::Num(idx)) if idx.fract() == 0.0 && idx >= 0.0 => {
+            path.push(PathPart::Index(idx as usize))
+          }
+          Some(Token::Str(key)) => path.push(PathPart::Key(key)),
+          _ => return Err("Expected an index or key in `[]`".into()),
+        }
+        self.expect("]")?;
+      } else {
+        return Ok(path);
+      }
+    }
+  }
+}
+
+/// A field's value as a number, numeric strings included (large balances are strings).
+fn as_number(value: &Value) -> Option<f64> {
+  match value {
+    Value::Number(num) => num.as_f64(),
+    Value::String(text) => text.parse().ok(),
+    _ => None,
+  }
+}
+
+fn as_text(value: &Value) -> Cow<'_, str> {
+  match value {
+    Value::String(text) => Cow::Borrowed(text),
+    value => Cow::Owned(value.to_string()),
+  }
+}
+
+fn compare(left: &Value, op: Op, right: &Value) -> bool {
+  let numbers = as_number(left).zip(as_number(right));
+  match op {
+    Op::Eq | Op::Ne => {
+      let equal = match numbers {
+        Some((left, right)) => left == right,
+        None => as_text(left) == as_text(right),
+      };
+      equal == matches!(op, Op::Eq)
+    }
+    Op::Gt => numbers.is_some_and(|(left, right)| left > right),
+    Op::Ge => numbers.is_some_and(|(left, right)| left >= right),
+    Op::Lt => numbers.is_some_and(|(left, right)| left < right),
+    Op::Le => numbers.is_some_and(|(left, right)| left <= right),
+    Op::Contains => match left {
+      Value::Array(items) => items.iter().any(|item| compare(item, Op::Eq, right)),
+      left => as_text(left).contains(as_text(right).as_ref()),
+    },
+    Op::StartsWith => as_text(left).starts_with(as_text(right).as_ref()),
+    Op::EndsWith => as_text(left).ends_with(as_text(right).as_ref()),
+  }
+}
+
+/// Parsed filter expression over the events feed.
+#[derive(Clone, Debug)]
+pub struct EventFilter(Expr);
+
+impl EventFilter {
+  pub fn parse(input: &str) -> Result<Self, String> {
+    let mut parser = Parser {
+      tokens: tokenize(input)?,
+      pos: 0,
+    };
+    let expr = parser.or()?;
+    match parser.peek() {
+      None => Ok(Self(expr)),
+      Some(token) => Err(format!("Unexpected {token:?}")),
+    }
+  }
+
+  pub fn matches(&self, event: &FilterEvent<'_>) -> bool {
+    Self::eval(&self.0, event)
+  }
+
+  fn eval(expr: &Expr, event: &FilterEvent<'_>) -> bool {
+    match expr {
+      Expr::And(left, right) => Self::eval(left, event) && Self::eval(right, event),
+      Expr::Or(left, right) => Self::eval(left, event) || Self::eval(right, event),
+      Expr::Not(expr) => !Self::eval(expr, event),
+      Expr::Compare(left, op, right) => {
+        match (Self::resolve(left, event), Self::resolve(right, event)) {
+          (Some(left), Some(right)) => compare(&left, *op, &right),
+          _ => false,
+        }
+      }
+      Expr::IsSet(field) => Self::field(field, event).is_some_and(|value| !value.is_null()),
+    }
+  }
+
+  fn resolve<'a>(operand: &'a Operand, event: &FilterEvent<'a>) -> Option<Cow<'a, Value>> {
+    match operand {
+      Operand::Field(field) => Self::field(field, event),
+      Operand::Literal(value) => Some(Cow::Borrowed(value)),
+    }
+  }
+
+  fn field<'a>(field: &Field, event: &FilterEvent<'a>) -> Option<Cow<'a, Value>> {
+    let value = match field {
+      Field::Name => event.name.into(),
+      Field::Pallet => event
+        .name
+        .split_once('.')
+        .map_or(event.name, |(pallet, _)| pallet)
+        .into(),
+      Field::Block => event.block.into(),
+      Field::Index => event.index.into(),
+      Field::Count => event.count.into(),
+      Field::Value(path) => {
+        let mut value = event.value?;
+        for part in path {
+          value = match part {
+            PathPart::Key(key) => value.get(key.as_str())?,
+            PathPart::Index(idx) => value.get(*idx)?,
+          };
+        }
+        return Some(Cow::Borrowed(value));
+      }
+    };
+    Some(Cow::Owned(value))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  /// Does the filter match event #3 of block 7, an `Asset.Issued` seen twice in the block?
+  fn matches(filter: &str, value: Option<&Value>) -> bool {
+    let filter = EventFilter::parse(filter).expect("valid filter");
+    filter.matches(&FilterEvent {
+      name: "Asset.Issued",
+      block: 7,
+      index: 3,
+      count: 2,
+      value,
+    })
+  }
+
+  fn parse_error(filter: &str) -> String {
+    EventFilter::parse(filter).expect_err("invalid filter")
+  }
+
+  #[test]
+  fn fields() {
+    assert!(matches("name == \"Asset.Issued\"", None));
+    assert!(matches("pallet == \"Asset\"", None));
+    assert!(matches("block == 7 && index == 3 && count == 2", None));
+    assert!(matches(
+      "name startsWith \"Asset.\" and name endsWith \"Issued\"",
+      None
+    ));
+    assert!(!matches("pallet == \"Balances\"", None));
+  }
+
+  #[test]
+  fn and_binds_tighter_than_or() {
+    // `true or (false and false)`, not `(true or false) and false`.
+    assert!(matches("block == 7 or block == 1 and block == 2", None));
+    assert!(!matches("(block == 7 or block == 1) and block == 2", None));
+  }
+
+  #[test]
+  fn not_binds_tighter_than_and() {
+    // `(not false) and true`.
+    assert!(matches("not block == 1 and block == 7", None));
+    assert!(!matches("not (block == 1 or block == 7)", None));
+    assert!(matches("!!(block == 7)", None));
+  }
+
+  #[test]
+  fn value_paths() {
+    let value = json!({ "x": [{ "ticker": "ACME" }, 5], "my key": true });
+    assert!(matches("value.x[0].ticker == \"ACME\"", Some(&value)));
+    assert!(matches("value.x[1] == 5", Some(&value)));
+    assert!(matches("value[\"my key\"]", Some(&value)));
+    assert!(matches("value.x contains 5", Some(&value)));
+    assert!(matches("value contains \"ACME\"", Some(&value)));
+  }
+
+  #[test]
+  fn numbers_and_strings() {
+    // Large balances are numeric strings, compared as numbers.
+    let value = json!({ "amount": "1000000", "ticker": "10" });
+    assert!(matches("value.amount > 999", Some(&value)));
+    assert!(matches("value.amount == 1000000.0", Some(&value)));
+    // Numbers compare numerically, not as text.
+    assert!(matches("value.ticker > 9", Some(&value)));
+    // Text doesn't order.
+    assert!(!matches("name > \"A\"", None));
+    assert!(matches("name != 1", None));
+  }
+
+  #[test]
+  fn missing_fields_dont_match() {
+    let value = json!({ "ticker": null });
+    assert!(!matches("value.ticker", Some(&value)));
+    assert!(!matches("value.amount", Some(&value)));
+    assert!(!matches("value.amount == 1", Some(&value)));
+    assert!(!matches("value.amount != 1", Some(&value)));
+    assert!(matches("not value.amount", Some(&value)));
+    // Blocks that aren't cached have no value.
+    assert!(!matches("value.ticker", None));
+  }
+
+  #[test]
+  fn parse_errors() {
+    assert_eq!(parse_error("nme == 1"), "Unknown field `nme`");
+    assert_eq!(parse_error("name == \"Asset"), "Unterminated string");
+    assert_eq!(parse_error("name == "), "Expected a field or value");
+    assert_eq!(parse_error("(block == 1"), "Expected `)`");
+    assert_eq!(
+      parse_error("block == 1 block"),
+      "Unexpected Ident(\"block\")"
+    );
+    assert_eq!(parse_error("value."), "Expected a key after `.`");
+    assert_eq!(parse_error("value[x]"), "Expected an index or key in `[]`");
+    assert_eq!(parse_error("1"), "Expected a comparison after 1.0");
+    assert_eq!(parse_error("name # 1"), "Unexpected '#'");
+    assert_eq!(parse_error("block == 1.2.3"), "Invalid number: 1.2.3");
+  }
+}
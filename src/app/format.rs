@@ -0,0 +1,183 @@
+use blake2::{Blake2b512, Digest};
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use egui::{ecolor::Hsva, Color32};
+
+use crate::backend::AccountKey;
+
+/// Polymesh SS58 address prefix.
+pub const POLYMESH_SS58_PREFIX: u16 = 12;
+
+/// SS58 prefixes are 14 bits.
+pub const MAX_SS58_PREFIX: u16 = 16383;
+
+/// POLYX has 6 decimals.
+const POLYX_DECIMALS: u32 = 6;
+
+/// Time zone used to show timestamps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum TimeZonePref {
+  #[default]
+  Local,
+  Utc,
+}
+
+/// Format a unix timestamp in milliseconds.
+pub fn format_time(ts: u64, tz: TimeZonePref) -> String {
+  let ts = ts as i64;
+  let time = match tz {
+    TimeZonePref::Local => Local
+      .timestamp_millis_opt(ts)
+      .single()
+      .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string()),
+    TimeZonePref::Utc => Utc
+      .timestamp_millis_opt(ts)
+      .single()
+      .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string()),
+  };
+  time.unwrap_or_else(|| format!("Invalid timestamp: {ts}"))
+}
+
+/// Hues of the pallet color palette.
+const PALLET_HUES: [f32; 12] = [
+  0.0, 0.08, 0.14, 0.22, 0.33, 0.42, 0.5, 0.58, 0.66, 0.75, 0.83, 0.92,
+];
+
+/// Stable color for an event's pallet, e.g. `Asset` for `Asset.AssetCreated`.
+///
+/// Brighter on dark themes and darker on light themes, so the text stays readable.
+pub fn pallet_color(name: &str, dark_mode: bool) -> Color32 {
+  let pallet = name.split('.').next().unwrap_or(name);
+  // FNV-1a, so the colors don't change between runs or builds.
+  let hash = pallet.bytes().fold(0x811c9dc5u32, |hash, b| {
+    (hash ^ b as u32).wrapping_mul(0x01000193)
+  });
+  let hue = PALLET_HUES[hash as usize % PALLET_HUES.len()];
+  let value = if dark_mode { 0.9 } else { 0.55 };
+  Hsva::new(hue, 0.6, value, 1.0).into()
+}
+
+/// Convert a date and time in the time zone to milliseconds since the unix epoch.
+pub fn to_timestamp(time: NaiveDateTime, tz: TimeZonePref) -> Option<u64> {
+  let ts = match tz {
+    TimeZonePref::Local => Local
+      .from_local_datetime(&time)
+      .earliest()?
+      .timestamp_millis(),
+    TimeZonePref::Utc => Utc.from_utc_datetime(&time).timestamp_millis(),
+  };
+  u64::try_from(ts).ok()
+}
+
+/// Format a duration in seconds, e.g. `1h 02m 03s`.
+pub fn format_duration(secs: i64) -> String {
+  let (hours, mins, secs) = (secs / 3600, (secs / 60) % 60, secs % 60);
+  if hours > 0 {
+    format!("{hours}h {mins:02}m {secs:02}s")
+  } else if mins > 0 {
+    format!("{mins}m {secs:02}s")
+  } else {
+    format!("{secs}s")
+  }
+}
+
+fn ss58_checksum(data: &[u8]) -> [u8; 2] {
+  let hash = Blake2b512::new()
+    .chain_update(b"SS58PRE")
+    .chain_update(data)
+    .finalize();
+  [hash[0], hash[1]]
+}
+
+/// Format an account as an SS58 address.
+pub fn format_account(account: &AccountKey, prefix: u16) -> String {
+  let mut data = match prefix {
+    0..=63 => vec![prefix as u8],
+    _ => {
+      // Two byte prefix encoding.
+      let first = ((prefix & 0b1111_1100) as u8 >> 2) | 0b0100_0000;
+      let second = (prefix >> 8) as u8 | ((prefix & 0b11) as u8) << 6;
+      vec![first, second]
+    }
+  };
+  data.extend(account);
+  let checksum = ss58_checksum(&data);
+  data.extend(checksum);
+  bs58::encode(data).into_string()
+}
+
+/// Parse an SS58 address (any prefix) or a `0x` hex account key.
+pub fn parse_account(input: &str) -> Option<AccountKey> {
+  let input = input.trim();
+  if let Some(hex) = input.strip_prefix("0x") {
+    return hex::decode(hex).ok()?.try_into().ok();
+  }
+  let data = bs58::decode(input).into_vec().ok()?;
+  let prefix_len = match data.first()? {
+    0..=63 => 1,
+    64..=127 => 2,
+    _ => return None,
+  };
+  if data.len() != prefix_len + 32 + 2 {
+    return None;
+  }
+  let (body, checksum) = data.split_at(data.len() - 2);
+  if ss58_checksum(body) != checksum {
+    return None;
+  }
+  body[prefix_len..].try_into().ok()
+}
+
+/// How amounts are shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum AmountFormat {
+  /// Token units, e.g. `1.500000 POLYX`.
+  #[default]
+  Token,
+  /// Token units and the raw integer, e.g. `1.500000 POLYX (1500000)`.
+  TokenAndRaw,
+}
+
+/// Format a POLYX balance.
+pub fn format_balance(amount: u128, format: AmountFormat) -> String {
+  let unit = 10u128.pow(POLYX_DECIMALS);
+  let token = format!("{}.{:06} POLYX", amount / unit, amount % unit);
+  match format {
+    AmountFormat::Token => token,
+    AmountFormat::TokenAndRaw => format!("{token} ({amount})"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// 2023-11-14 22:13:20.5 UTC.
+  const TS: u64 = 1_700_000_000_500;
+
+  #[test]
+  fn format_time_utc() {
+    assert_eq!(
+      format_time(TS, TimeZonePref::Utc),
+      "2023-11-14 22:13:20 UTC"
+    );
+  }
+
+  #[test]
+  fn format_time_local() {
+    // Depends on the machine's time zone, so compare with chrono's local time.
+    let local = Local.timestamp_millis_opt(TS as i64).unwrap();
+    assert_eq!(
+      format_time(TS, TimeZonePref::Local),
+      local.format("%Y-%m-%d %H:%M:%S").to_string()
+    );
+  }
+
+  #[test]
+  fn format_time_invalid() {
+    let ts = i64::MAX as u64;
+    assert_eq!(
+      format_time(ts, TimeZonePref::Utc),
+      format!("Invalid timestamp: {ts}")
+    );
+  }
+}
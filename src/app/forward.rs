@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::backend::BlockInfo;
+
+/// Events sent per request.
+const BATCH_SIZE: usize = 500;
+/// How long events wait for a batch to fill up.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+/// Events kept while the endpoint is down, the oldest are dropped first.
+const MAX_PENDING: usize = 50_000;
+/// Failed batches are retried after this, doubling up to `MAX_RETRY_DELAY`.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Body format of the forwarded events.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ForwardFormat {
+  /// Loki push API, e.g. `http://localhost:3100/loki/api/v1/push`.
+  #[default]
+  Loki,
+  /// One JSON object per line.
+  JsonLines,
+}
+
+/// Forwarded event: timestamp in nanoseconds (for Loki) and the JSON line.
+type Line = (u64, String);
+
+/// Forwards decoded events to a log endpoint, e.g. Loki.
+///
+/// Events are batched and sent from a thread, failed batches are retried until the endpoint is
+/// back and batches the endpoint rejects are dropped.  The thread stops when the forwarder is
+/// dropped.
+pub struct EventForwarder {
+  tx: mpsc::Sender<Line>,
+}
+
+impl EventForwarder {
+  pub fn start(url: String, format: ForwardFormat) -> Self {
+    let (tx, rx) = mpsc::channel();
+    log::info!("Forwarding events to {url}");
+    std::thread::spawn(move || Self::run(&url, format, rx));
+    Self { tx }
+  }
+
+  /// Queue the events of a loaded block.
+  pub fn push_block(&self, block: &BlockInfo) {
+    let time = block
+      .timestamp
+      .unwrap_or_else(|| chrono::Utc::now().timestamp_millis() as u64);
+    for event in &block.events {
+      let line = json!({
+        "block": event.block,
+        "hash": block.hash,
+        "timestamp": block.timestamp,
+        "index": event.number,
+        "name": event.name,
+        "value": event.value,
+      });
+      let _ = self.tx.send((time * 1_000_000, line.to_string()));
+    }
+  }
+
+  fn run(url: &str, format: ForwardFormat, rx: mpsc::Receiver<Line>) {
+    let mut pending = VecDeque::new();
+    // `None` while the endpoint works.
+    let mut retry_delay = None;
+    let mut next_send = Instant::now() + FLUSH_INTERVAL;
+    loop {
+      let timeout = next_send.saturating_duration_since(Instant::now());
+      match rx.recv_timeout(timeout) {
+        Ok(line) => {
+          pending.push_back(line);
+          if pending.len() > MAX_PENDING {
+            pending.pop_front();
+          }
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => (),
+        // Forwarder dropped.
+        Err(mpsc::RecvTimeoutError::Disconnected) => return,
+      }
+      // Full batches go out early, unless waiting to retry.
+      let full = pending.len() >= BATCH_SIZE && retry_delay.is_none();
+      if Instant::now() < next_send && !full {
+        continue;
+      }
+      next_send = Instant::now() + FLUSH_INTERVAL;
+      while !pending.is_empty() {
+        let len = pending.len().min(BATCH_SIZE);
+        match send_batch(url, format, pending.range(..len)) {
+          Ok(()) => {
+            pending.drain(..len);
+            retry_delay = None;
+          }
+          Err(SendError::Rejected(code)) => {
+            log::error!("Endpoint rejected {len} events with status {code}, dropping them");
+            pending.drain(..len);
+          }
+          Err(SendError::Failed(err)) => {
+            let delay = retry_delay.map_or(RETRY_DELAY, |delay: Duration| {
+              (delay * 2).min(MAX_RETRY_DELAY)
+            });
+            log::warn!("Failed to forward {len} events, retrying in {delay:?}: {err}");
+            next_send = Instant::now() + delay;
+            retry_delay = Some(delay);
+            break;
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Why a batch wasn't sent.
+enum SendError {
+  /// The endpoint rejected the batch (e.g. a 4xx), sending it again won't help.
+  Rejected(u16),
+  /// Transport error or 5xx, retried until the endpoint is back.
+  Failed(String),
+}
+
+fn send_batch<'a>(
+  url: &str,
+  format: ForwardFormat,
+  lines: impl Iterator<Item = &'a Line>,
+) -> Result<(), SendError> {
+  let request = ureq::post(url).timeout(SEND_TIMEOUT);
+  let res = match format {
+    ForwardFormat::Loki => {
+      let values: Vec<Value> = lines
+        .map(|(time, line)| json!([time.to_string(), line]))
+        .collect();
+      request.send_json(json!({
+        "streams": [{
+          "stream": { "job": env!("CARGO_PKG_NAME") },
+          "values": values,
+        }]
+      }))
+    }
+    ForwardFormat::JsonLines => {
+      let body: String = lines.map(|(_, line)| format!("{line}\n")).collect();
+      request
+        .set("Content-Type", "application/x-ndjson")
+        .send_string(&body)
+    }
+  };
+  res.map(|_| ()).map_err(|err| match err {
+    ureq::Error::Status(code, _) if code < 500 => SendError::Rejected(code),
+    err => SendError::Failed(err.to_string()),
+  })
+}
@@ -0,0 +1,172 @@
+use std::hash::Hash;
+
+use egui::*;
+
+const HEADER_HEIGHT: f32 = 20.0;
+
+/// Table with its leftmost columns frozen, the other columns scroll horizontally.
+///
+/// The frozen and scrolled columns are two side by side scroll areas sharing their vertical
+/// offset.  The last column grows to fit its widest cell.
+pub struct FrozenTable {
+  id: Id,
+  /// Header and width of each column.
+  columns: Vec<(&'static str, f32)>,
+  frozen: usize,
+  row_height: f32,
+}
+
+impl FrozenTable {
+  pub fn new(id_source: impl Hash, frozen: usize, row_height: f32) -> Self {
+    Self {
+      id: Id::new(id_source),
+      columns: Vec::new(),
+      frozen,
+      row_height,
+    }
+  }
+
+  pub fn column(mut self, header: &'static str, width: f32) -> Self {
+    self.columns.push((header, width));
+    self
+  }
+
+  /// `cell_ui` is called with the row and column index of each visible cell.
+  pub fn show(self, ui: &mut Ui, num_rows: usize, mut cell_ui: impl FnMut(&mut Ui, usize, usize)) {
+    let offset_id = self.id.with("offset");
+    let width_id = self.id.with("last_width");
+    let offset: f32 = ui.data(|d| d.get_temp(offset_id)).unwrap_or_default();
+    let last_width: f32 = ui.data(|d| d.get_temp(width_id)).unwrap_or_default();
+    let frozen = self.frozen.min(self.columns.len().saturating_sub(1));
+    let mut widths: Vec<f32> = self.columns.iter().map(|(_, width)| *width).collect();
+    if let Some(width) = widths.last_mut() {
+      *width = width.max(last_width);
+    }
+    let mut new_offset = offset;
+    let mut content_width = last_width;
+    ui.horizontal_top(|ui| {
+      if frozen > 0 {
+        let (scrolled, _) = self.columns_ui(ui, 0..frozen, &widths, offset, num_rows, &mut cell_ui);
+        if scrolled != offset {
+          new_offset = scrolled;
+        }
+        ui.separator();
+      }
+      ScrollArea::horizontal()
+        .id_source(self.id.with("hscroll"))
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+          let (scrolled, width) = self.columns_ui(
+            ui,
+            frozen..widths.len(),
+            &widths,
+            offset,
+            num_rows,
+            &mut cell_ui,
+          );
+          if scrolled != offset {
+            new_offset = scrolled;
+          }
+          content_width = content_width.max(width);
+        });
+    });
+    if new_offset != offset {
+      // Let the other side catch up.
+      ui.ctx().request_repaint();
+    }
+    ui.data_mut(|d| {
+      d.insert_temp(offset_id, new_offset);
+      d.insert_temp(width_id, content_width);
+    });
+  }
+
+  /// Header and rows of some columns, returns the new vertical scroll offset and the width of
+  /// the widest visible cell of the last column.
+  fn columns_ui(
+    &self,
+    ui: &mut Ui,
+    columns: std::ops::Range<usize>,
+    widths: &[f32],
+    offset: f32,
+    num_rows: usize,
+    cell_ui: &mut impl FnMut(&mut Ui, usize, usize),
+  ) -> (f32, f32) {
+    let first = columns.start;
+    // The scroll bar is only shown on the scrolled columns.
+    let visibility = if first == 0 && self.frozen > 0 {
+      scroll_area::ScrollBarVisibility::AlwaysHidden
+    } else {
+      scroll_area::ScrollBarVisibility::VisibleWhenNeeded
+    };
+    ui.vertical(|ui| {
+      let mut last_width = self.row_ui(
+        ui,
+        HEADER_HEIGHT,
+        false,
+        columns.clone(),
+        widths,
+        |ui, col| {
+          ui.heading(self.columns[col].0);
+        },
+      );
+      let offset = ScrollArea::vertical()
+        .id_source(self.id.with(first))
+        .vertical_scroll_offset(offset)
+        .scroll_bar_visibility(visibility)
+        .auto_shrink([true, false])
+        .show_rows(ui, self.row_height, num_rows, |ui, row_range| {
+          for row in row_range {
+            let stripe = row % 2 == 1;
+            let width = self.row_ui(
+              ui,
+              self.row_height,
+              stripe,
+              columns.clone(),
+              widths,
+              |ui, col| cell_ui(ui, row, col),
+            );
+            last_width = last_width.max(width);
+          }
+        })
+        .state
+        .offset
+        .y;
+      (offset, last_width)
+    })
+    .inner
+  }
+
+  /// Cells of one row, clipped to their column.  Returns the content width of the last
+  /// column's cell, 0 if it isn't in `columns`.
+  fn row_ui(
+    &self,
+    ui: &mut Ui,
+    height: f32,
+    stripe: bool,
+    columns: std::ops::Range<usize>,
+    widths: &[f32],
+    mut cell_ui: impl FnMut(&mut Ui, usize),
+  ) -> f32 {
+    let spacing = ui.spacing().item_spacing.x;
+    let width: f32 = widths[columns.clone()].iter().sum::<f32>()
+      + spacing * columns.len().saturating_sub(1) as f32;
+    let (rect, _) = ui.allocate_exact_size(vec2(width, height), Sense::hover());
+    if stripe {
+      ui.painter()
+        .rect_filled(rect, 0.0, ui.visuals().faint_bg_color);
+    }
+    let mut left = rect.left();
+    let mut last_width = 0.0;
+    for col in columns {
+      let cell_rect = Rect::from_min_size(pos2(left, rect.top()), vec2(widths[col], height));
+      let mut cell = ui.child_ui(cell_rect, Layout::left_to_right(Align::Center));
+      cell.set_clip_rect(cell_rect.intersect(ui.clip_rect()));
+      cell_ui(&mut cell, col);
+      if col == widths.len() - 1 {
+        last_width = cell.min_rect().width();
+      }
+      left += widths[col] + spacing;
+    }
+    last_width
+  }
+}
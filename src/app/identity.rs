@@ -0,0 +1,387 @@
+use egui::*;
+use serde_json::Value;
+
+use super::account::account_anchor;
+use super::format::*;
+use super::staking::{raw_value_ui, value_account};
+use super::{copied_ui, did_events_anchor, SubAppEvent};
+use crate::backend::*;
+
+#[derive(Clone, Copy)]
+enum FieldKind {
+  Did,
+  /// `Option<IdentityId>`.
+  OptionDid,
+  Account,
+  OptionAccount,
+  Claim,
+  Authorization,
+  AuthId,
+  /// Time in milliseconds since the unix epoch.
+  Moment,
+  OptionMoment,
+  Ticker,
+  Other,
+}
+
+/// Fields of the identity events with unnamed fields.
+const IDENTITY_EVENTS: &[(&str, &[(&str, FieldKind)])] = &[
+  (
+    "Identity.DidCreated",
+    &[
+      ("DID", FieldKind::Did),
+      ("Primary key", FieldKind::Account),
+      ("Secondary keys", FieldKind::Other),
+    ],
+  ),
+  (
+    "Identity.ChildDidCreated",
+    &[
+      ("Parent DID", FieldKind::Did),
+      ("Child DID", FieldKind::Did),
+      ("Primary key", FieldKind::Account),
+    ],
+  ),
+  (
+    "Identity.ChildDidUnlinked",
+    &[
+      ("Caller DID", FieldKind::Did),
+      ("Parent DID", FieldKind::Did),
+      ("Child DID", FieldKind::Did),
+    ],
+  ),
+  (
+    "Identity.SecondaryKeysAdded",
+    &[("DID", FieldKind::Did), ("Keys", FieldKind::Other)],
+  ),
+  (
+    "Identity.SecondaryKeysRemoved",
+    &[("DID", FieldKind::Did), ("Keys", FieldKind::Other)],
+  ),
+  (
+    "Identity.SecondaryKeyLeftIdentity",
+    &[("DID", FieldKind::Did), ("Key", FieldKind::Account)],
+  ),
+  (
+    "Identity.SecondaryKeyPermissionsUpdated",
+    &[
+      ("DID", FieldKind::Did),
+      ("Key", FieldKind::Account),
+      ("Old permissions", FieldKind::Other),
+      ("New permissions", FieldKind::Other),
+    ],
+  ),
+  (
+    "Identity.PrimaryKeyUpdated",
+    &[
+      ("DID", FieldKind::Did),
+      ("Old key", FieldKind::Account),
+      ("New key", FieldKind::Account),
+    ],
+  ),
+  (
+    "Identity.ClaimAdded",
+    &[("DID", FieldKind::Did), ("Claim", FieldKind::Claim)],
+  ),
+  (
+    "Identity.ClaimRevoked",
+    &[("DID", FieldKind::Did), ("Claim", FieldKind::Claim)],
+  ),
+  (
+    "Identity.AssetDidRegistered",
+    &[("DID", FieldKind::Did), ("Ticker", FieldKind::Ticker)],
+  ),
+  (
+    "Identity.AuthorizationAdded",
+    &[
+      ("From", FieldKind::Did),
+      ("To DID", FieldKind::OptionDid),
+      ("To key", FieldKind::OptionAccount),
+      ("Authorization", FieldKind::AuthId),
+      ("Kind", FieldKind::Authorization),
+      ("Expiry", FieldKind::OptionMoment),
+    ],
+  ),
+  (
+    "Identity.AuthorizationRevoked",
+    &[
+      ("To DID", FieldKind::OptionDid),
+      ("To key", FieldKind::OptionAccount),
+      ("Authorization", FieldKind::AuthId),
+    ],
+  ),
+  (
+    "Identity.AuthorizationRejected",
+    &[
+      ("To DID", FieldKind::OptionDid),
+      ("To key", FieldKind::OptionAccount),
+      ("Authorization", FieldKind::AuthId),
+    ],
+  ),
+  (
+    "Identity.AuthorizationConsumed",
+    &[
+      ("To DID", FieldKind::OptionDid),
+      ("To key", FieldKind::OptionAccount),
+      ("Authorization", FieldKind::AuthId),
+    ],
+  ),
+  (
+    "Identity.CddClaimsInvalidated",
+    &[
+      ("DID", FieldKind::Did),
+      ("Invalidated at", FieldKind::Moment),
+    ],
+  ),
+  ("Identity.SecondaryKeysFrozen", &[("DID", FieldKind::Did)]),
+  ("Identity.SecondaryKeysUnfrozen", &[("DID", FieldKind::Did)]),
+];
+
+/// Labeled fields of an identity event, `None` for unknown identity events.
+fn identity_fields(event: &EventInfo) -> Option<Vec<(&'static str, FieldKind, &Value)>> {
+  let (_, fields) = IDENTITY_EVENTS
+    .iter()
+    .find(|(name, _)| *name == event.name)?;
+  let values = match &event.value {
+    Value::Array(values) => values.iter().collect(),
+    value => vec![value],
+  };
+  if values.len() != fields.len() {
+    return None;
+  }
+  Some(
+    fields
+      .iter()
+      .zip(values)
+      .map(|((name, kind), value)| (*name, *kind, value))
+      .collect(),
+  )
+}
+
+/// Split an enum variant name into words, e.g. `CustomerDueDiligence` -> `Customer due diligence`.
+fn variant_label(variant: &str) -> String {
+  let mut label = String::new();
+  for (idx, c) in variant.chars().enumerate() {
+    if idx > 0 && c.is_uppercase() {
+      label.push(' ');
+      label.extend(c.to_lowercase());
+    } else {
+      label.push(c);
+    }
+  }
+  label
+}
+
+/// Variant name and data of a serialized enum, `{"Variant": data}` or `"Variant"`.
+fn enum_variant(value: &Value) -> Option<(&str, Option<&Value>)> {
+  match value {
+    Value::String(variant) => Some((variant, None)),
+    Value::Object(map) if map.len() == 1 => map
+      .iter()
+      .next()
+      .map(|(variant, data)| (variant.as_str(), Some(data))),
+    _ => None,
+  }
+}
+
+/// Tickers are 12 bytes, serialized as a byte array or hex, padded with zeros.
+fn ticker_text(value: &Value) -> Option<String> {
+  let bytes = match value {
+    Value::Array(bytes) => bytes
+      .iter()
+      .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+      .collect::<Option<Vec<u8>>>()?,
+    Value::String(ticker) => match ticker.strip_prefix("0x") {
+      Some(hex) => hex::decode(hex).ok()?,
+      None => return Some(ticker.clone()),
+    },
+    _ => return None,
+  };
+  let ticker = String::from_utf8(bytes).ok()?;
+  Some(ticker.trim_end_matches('\0').to_string())
+}
+
+/// DIDs are hex strings, linked to the events feed filtered to the DID, with a button to copy
+/// them.
+fn did_ui(ui: &mut Ui, value: &Value, app_event: &mut Option<SubAppEvent>) {
+  let Some(did) = value.as_str() else {
+    raw_value_ui(ui, value);
+    return;
+  };
+  ui.horizontal(|ui| {
+    let link = ui
+      .link(RichText::new(did).monospace())
+      .on_hover_text("Show the recent events of this DID");
+    if link.clicked() {
+      *app_event = Some(SubAppEvent::OpenApp(did_events_anchor(did)));
+    }
+    let button = ui.small_button("📋").on_hover_text("Copy DID");
+    if button.clicked() {
+      ui.output_mut(|o| o.copied_text = did.to_string());
+    }
+    copied_ui(ui, &button, button.clicked());
+  });
+}
+
+fn account_ui(ui: &mut Ui, value: &Value, ss58_prefix: u16, app_event: &mut Option<SubAppEvent>) {
+  match value_account(value) {
+    Some(account) => {
+      if ui.link(format_account(&account, ss58_prefix)).clicked() {
+        *app_event = Some(SubAppEvent::OpenApp(account_anchor(&account)));
+      }
+    }
+    None => raw_value_ui(ui, value),
+  }
+}
+
+/// Claim scope, e.g. `{"Ticker": ...}`.
+fn scope_text(scope: &Value) -> String {
+  match enum_variant(scope) {
+    Some(("Ticker", Some(ticker))) => {
+      format!(
+        "ticker {}",
+        ticker_text(ticker).unwrap_or_else(|| ticker.to_string())
+      )
+    }
+    Some(("Identity", Some(Value::String(did)))) => format!("identity {did}"),
+    Some((variant, Some(data))) => format!("{} {data}", variant_label(variant).to_lowercase()),
+    _ => scope.to_string(),
+  }
+}
+
+/// `IdentityClaim`: the claim type and scope, the issuer and the dates.
+fn claim_ui(ui: &mut Ui, claim: &Value, tz: TimeZonePref, app_event: &mut Option<SubAppEvent>) {
+  let Value::Object(fields) = claim else {
+    raw_value_ui(ui, claim);
+    return;
+  };
+  ui.vertical(|ui| {
+    match fields.get("claim").and_then(enum_variant) {
+      Some((variant, data)) => {
+        let label = variant_label(variant);
+        let scope = match data {
+          // `Jurisdiction(country, scope)`.
+          Some(Value::Array(data)) => data.last().map(scope_text),
+          Some(Value::Null) | None => None,
+          Some(scope) => Some(scope_text(scope)),
+        };
+        match scope {
+          Some(scope) => ui.strong(format!("{label}, scope: {scope}")),
+          None => ui.strong(label),
+        };
+        if let (Some(Value::Array(data)), "Jurisdiction") = (data, variant) {
+          if let Some(country) = data.first().and_then(|country| country.as_str()) {
+            ui.label(format!("Country: {country}"));
+          }
+        }
+      }
+      None => {
+        ui.label("Unknown claim");
+      }
+    }
+    if let Some(issuer) = fields.get("claim_issuer") {
+      ui.horizontal(|ui| {
+        ui.label("Issuer:");
+        did_ui(ui, issuer, app_event);
+      });
+    }
+    let time = |name: &str| fields.get(name).and_then(|time| time.as_u64());
+    if let Some(issued) = time("issuance_date") {
+      ui.label(format!("Issued: {}", format_time(issued, tz)));
+    }
+    match time("expiry") {
+      Some(expiry) => ui.label(format!("Expires: {}", format_time(expiry, tz))),
+      None => ui.weak("Doesn't expire"),
+    };
+  });
+}
+
+/// `AuthorizationData`: the authorization kind with its data.
+fn authorization_ui(
+  ui: &mut Ui,
+  auth: &Value,
+  ss58_prefix: u16,
+  app_event: &mut Option<SubAppEvent>,
+) {
+  let Some((variant, data)) = enum_variant(auth) else {
+    raw_value_ui(ui, auth);
+    return;
+  };
+  ui.vertical(|ui| {
+    ui.strong(variant_label(variant));
+    match (variant, data) {
+      (_, None | Some(Value::Null)) => (),
+      ("TransferTicker" | "TransferAssetOwnership", Some(ticker)) => {
+        ui.label(format!(
+          "Ticker: {}",
+          ticker_text(ticker).unwrap_or_else(|| ticker.to_string())
+        ));
+      }
+      ("AddMultiSigSigner" | "AddRelayerPayingKey", Some(data)) => {
+        // The first field is the account.
+        let account = match data {
+          Value::Array(fields) => fields.first().unwrap_or(data),
+          data => data,
+        };
+        account_ui(ui, account, ss58_prefix, app_event);
+      }
+      ("AttestPrimaryKeyRotation", Some(did)) => did_ui(ui, did, app_event),
+      (_, Some(data)) => raw_value_ui(ui, data),
+    }
+  });
+}
+
+/// Identity event fields with claim types, scopes and authorization kinds labeled, and accounts
+/// linked to the account sub-app.  Returns `false` for unknown identity events, which should
+/// use the generic tree.
+pub fn identity_event_ui(
+  ui: &mut Ui,
+  event: &EventInfo,
+  ss58_prefix: u16,
+  tz: TimeZonePref,
+  app_event: &mut Option<SubAppEvent>,
+) -> bool {
+  let Some(fields) = identity_fields(event) else {
+    return false;
+  };
+  Grid::new(("identity_event", event.block, event.number))
+    .num_columns(2)
+    .striped(true)
+    .show(ui, |ui| {
+      for (name, kind, value) in fields {
+        ui.label(format!("{name}:"));
+        match (kind, value) {
+          (
+            FieldKind::OptionDid | FieldKind::OptionAccount | FieldKind::OptionMoment,
+            Value::Null,
+          ) => {
+            ui.weak("—");
+          }
+          (FieldKind::Did | FieldKind::OptionDid, value) => did_ui(ui, value, app_event),
+          (FieldKind::Account | FieldKind::OptionAccount, value) => {
+            account_ui(ui, value, ss58_prefix, app_event)
+          }
+          (FieldKind::Claim, claim) => claim_ui(ui, claim, tz, app_event),
+          (FieldKind::Authorization, auth) => authorization_ui(ui, auth, ss58_prefix, app_event),
+          (FieldKind::AuthId, Value::Number(id)) => {
+            ui.label(format!("#{id}"));
+          }
+          (FieldKind::Moment | FieldKind::OptionMoment, Value::Number(time)) => {
+            match time.as_u64() {
+              Some(time) => ui.label(format_time(time, tz)),
+              None => ui.monospace(time.to_string()),
+            };
+          }
+          (FieldKind::Ticker, ticker) => match ticker_text(ticker) {
+            Some(ticker) => {
+              ui.monospace(ticker);
+            }
+            None => raw_value_ui(ui, ticker),
+          },
+          (_, value) => raw_value_ui(ui, value),
+        }
+        ui.end_row();
+      }
+    });
+  true
+}
@@ -0,0 +1,127 @@
+use egui::*;
+use serde_json::Value;
+
+use crate::backend::BlockNumber;
+
+/// Default number of JSON nodes above which a value is only rendered on request.
+pub const DEFAULT_LARGE_JSON_NODES: usize = 500;
+/// Default number of levels of a JSON tree that start expanded.
+pub const DEFAULT_JSON_EXPAND_DEPTH: usize = 1;
+
+/// Check if `value` has more than `limit` nodes, without walking the whole value.
+fn exceeds_nodes(value: &Value, limit: &mut usize) -> bool {
+  if *limit == 0 {
+    return true;
+  }
+  *limit -= 1;
+  match value {
+    Value::Array(items) => items.iter().any(|item| exceeds_nodes(item, limit)),
+    Value::Object(map) => map.values().any(|item| exceeds_nodes(item, limit)),
+    _ => false,
+  }
+}
+
+/// Field names that hold block numbers, e.g. the scheduler's `when`.
+fn is_block_number_field(name: &str) -> bool {
+  let name = name.to_lowercase();
+  name.contains("block") || matches!(name.as_str(), "when" | "at" | "height")
+}
+
+fn value_ui(
+  ui: &mut Ui,
+  id: Id,
+  name: &str,
+  value: &Value,
+  open_depth: usize,
+  clicked_block: &mut Option<BlockNumber>,
+) {
+  let open = open_depth > 0;
+  let child_depth = open_depth.saturating_sub(1);
+  match value {
+    Value::Array(items) => {
+      CollapsingHeader::new(format!("{name} [{}]", items.len()))
+        .id_source(id)
+        .default_open(open)
+        .show(ui, |ui| {
+          for (idx, item) in items.iter().enumerate() {
+            value_ui(
+              ui,
+              id.with(idx),
+              &idx.to_string(),
+              item,
+              child_depth,
+              clicked_block,
+            );
+          }
+        });
+    }
+    Value::Object(map) => {
+      CollapsingHeader::new(format!("{name} {{{}}}", map.len()))
+        .id_source(id)
+        .default_open(open)
+        .show(ui, |ui| {
+          for (key, item) in map {
+            value_ui(ui, id.with(key), key, item, child_depth, clicked_block);
+          }
+        });
+    }
+    value => {
+      ui.horizontal(|ui| {
+        ui.label(format!("{name}:"));
+        let number = value
+          .as_u64()
+          .filter(|_| is_block_number_field(name))
+          .and_then(|number| BlockNumber::try_from(number).ok());
+        match number {
+          Some(number) => {
+            if ui
+              .link(RichText::new(number.to_string()).monospace())
+              .on_hover_text("Go to block")
+              .clicked()
+            {
+              *clicked_block = Some(number);
+            }
+          }
+          None => {
+            ui.label(RichText::new(value.to_string()).monospace());
+          }
+        }
+      });
+    }
+  }
+}
+
+/// Collapsible tree of a JSON value.
+///
+/// The first `expand_depth` levels start expanded.  Values with more than `large_nodes` nodes
+/// start collapsed behind a button, so they don't slow down every frame until the user asks to
+/// see them.
+///
+/// Fields named like block numbers are links, returns the block number the user clicked.
+pub fn json_tree_ui(
+  ui: &mut Ui,
+  id: impl std::hash::Hash,
+  name: &str,
+  value: &Value,
+  large_nodes: usize,
+  expand_depth: usize,
+) -> Option<BlockNumber> {
+  let id = ui.make_persistent_id(id);
+  let expanded_id = id.with("expanded");
+  let expanded = ui.data(|d| d.get_temp::<bool>(expanded_id).unwrap_or(false));
+  let mut limit = large_nodes;
+  if !expanded && exceeds_nodes(value, &mut limit) {
+    if ui
+      .button(format!("{name}: large value, click to expand"))
+      .clicked()
+    {
+      ui.data_mut(|d| d.insert_temp(expanded_id, true));
+    }
+    return None;
+  }
+  // Large values stay collapsed until the user opens the parts they need.
+  let mut clicked_block = None;
+  let depth = if expanded { 0 } else { expand_depth };
+  value_ui(ui, id, name, value, depth, &mut clicked_block);
+  clicked_block
+}
@@ -0,0 +1,89 @@
+use std::fmt::{Display, Write as _};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+/// Default port of the metrics endpoint.  Substrate nodes use 9615 for their own metrics.
+pub const DEFAULT_METRICS_PORT: u16 = 9616;
+/// How often the server checks if it was stopped, while waiting for connections.
+const ACCEPT_POLL: Duration = Duration::from_millis(200);
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Append a metric in the Prometheus text format.
+pub fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl Display) {
+  let _ = writeln!(out, "# HELP {name} {help}");
+  let _ = writeln!(out, "# TYPE {name} {kind}");
+  let _ = writeln!(out, "{name} {value}");
+}
+
+/// Tiny HTTP server for Prometheus scrapes of `/metrics`.
+///
+/// The UI updates the metrics snapshot, the server thread only serves the latest snapshot.
+/// The server stops when it is dropped.
+pub struct MetricsServer {
+  snapshot: Arc<Mutex<String>>,
+}
+
+impl MetricsServer {
+  /// Listen on localhost, put a reverse proxy in front to expose the metrics.
+  pub fn start(port: u16) -> std::io::Result<Self> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    // Non-blocking, so the thread notices when the server is dropped.
+    listener.set_nonblocking(true)?;
+    let snapshot = Arc::new(Mutex::new(String::new()));
+    let weak = Arc::downgrade(&snapshot);
+    std::thread::spawn(move || Self::run(listener, weak));
+    log::info!("Serving metrics on http://127.0.0.1:{port}/metrics");
+    Ok(Self { snapshot })
+  }
+
+  pub fn update(&self, metrics: String) {
+    *self.snapshot.lock().unwrap() = metrics;
+  }
+
+  fn run(listener: TcpListener, snapshot: Weak<Mutex<String>>) {
+    loop {
+      let Some(snapshot) = snapshot.upgrade() else {
+        // Server dropped.
+        return;
+      };
+      match listener.accept() {
+        Ok((stream, _)) => {
+          if let Err(err) = Self::serve(stream, &snapshot) {
+            log::debug!("Metrics request failed: {err:?}");
+          }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+          drop(snapshot);
+          std::thread::sleep(ACCEPT_POLL);
+        }
+        Err(err) => {
+          log::error!("Metrics server stopped: {err:?}");
+          return;
+        }
+      }
+    }
+  }
+
+  fn serve(stream: TcpStream, snapshot: &Mutex<String>) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    // Request line, e.g. `GET /metrics HTTP/1.1`.  The headers are ignored.
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+    let (status, body) = match path {
+      "/metrics" => ("200 OK", snapshot.lock().unwrap().clone()),
+      _ => ("404 Not Found", "Not found, try /metrics\n".to_string()),
+    };
+    let mut stream = reader.into_inner();
+    write!(
+      stream,
+      "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+      body.len()
+    )?;
+    stream.flush()
+  }
+}
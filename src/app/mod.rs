@@ -1,15 +1,138 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ops::Range;
 
+use codec::{Compact, Encode};
 use egui::*;
 use egui_extras::{Column, Size, StripBuilder, TableBuilder};
 
 use crate::backend::*;
 
+mod format;
+use format::*;
+mod settings;
+use settings::Settings;
+mod stats;
+use stats::{BlockRate, SessionStats};
+mod dashboard;
+use dashboard::DashboardApp;
+mod account;
+use account::{account_anchor, AccountApp};
+mod json;
+use json::json_tree_ui;
+mod calls;
+use calls::block_calls_ui;
+mod staking;
+use staking::staking_event_ui;
+mod identity;
+use identity::identity_event_ui;
+mod frozen_table;
+use frozen_table::FrozenTable;
+mod call_index;
+use call_index::{CallIndex, CallIndexApp};
+mod constants;
+use constants::ConstantsApp;
+mod filter;
+use filter::{EventFilter, FilterEvent, FILTER_HELP};
+mod download;
+use download::save_file;
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+mod metrics;
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+use metrics::{write_metric, MetricsServer};
+mod view_state;
+use view_state::ViewState;
+mod bookmarks;
+use bookmarks::Bookmark;
+#[cfg(not(target_arch = "wasm32"))]
+mod forward;
+#[cfg(not(target_arch = "wasm32"))]
+use forward::{EventForwarder, ForwardFormat};
+#[cfg(not(target_arch = "wasm32"))]
+mod update_check;
+#[cfg(not(target_arch = "wasm32"))]
+use update_check::UpdateCheck;
+#[cfg(test)]
+mod tests;
+
 const POLYMESH_STAGING: &str = "wss://staging-rpc.polymesh.live";
 const POLYMESH_TESTNET: &str = "wss://testnet-rpc.polymesh.live";
-const MAX_BACKEND_UPDATES: usize = 100;
+const LOCAL_NODE: &str = "ws://localhost:9944/";
+
+/// Well-known Polymesh network.
+struct Network {
+  name: &'static str,
+  url: &'static str,
+  /// Genesis hash the network's nodes must have.  `None` for networks that are reset from time
+  /// to time.
+  genesis: Option<&'static str>,
+}
+
+const NETWORKS: &[Network] = &[
+  Network {
+    name: "Polymesh Mainnet",
+    url: "wss://mainnet-rpc.polymesh.network",
+    genesis: Some("0x6fbd74e5e1d0a61d52ccfe9d4adaed16dd3a7caa37c6bc4d0c2fa12e8b2f4063"),
+  },
+  Network {
+    name: "Polymesh Testnet",
+    url: POLYMESH_TESTNET,
+    genesis: None,
+  },
+  Network {
+    name: "Polymesh Staging",
+    url: POLYMESH_STAGING,
+    genesis: None,
+  },
+  Network {
+    name: "Local",
+    url: LOCAL_NODE,
+    genesis: None,
+  },
+];
+const IDLE_REPAINT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
 const MAX_RECENT_BLOCKS: usize = 2000;
 const MAX_RECENT_EVENTS: usize = 2000;
+/// Number of errors kept in the error log.
+const MAX_ERROR_LOG: usize = 200;
+/// Anchors kept in the back history.
+const MAX_HISTORY: usize = 100;
+/// Longest url anchor accepted, shared view codes with many pins are the longest.
+const MAX_ANCHOR_LEN: usize = 8192;
+/// Frames in a row that run out of update budget before the backend updates are summarized to
+/// catch up with the chain.
+const LAGGING_FRAMES: u32 = 30;
+/// How long "Copied!" is shown after copying to the clipboard, in seconds.
+const COPIED_FEEDBACK: f64 = 1.5;
+/// Seconds between saves, changes in between are saved together.
+const MIN_SAVE_INTERVAL: f64 = 2.0;
+/// Saved state size that hints at a cache missing `#[serde(skip)]`.
+const MAX_SAVED_STATE: usize = 256 * 1024;
+/// How long a new best block stays highlighted, in seconds.
+const BEST_BLOCK_FLASH: f64 = 1.0;
+/// Seconds between `ChainStatus` requests, while it is shown.
+const CHAIN_STATUS_INTERVAL: f64 = 5.0;
+/// Pallets that always get a quick-filter chip in the events feed.
+const DEFAULT_PALLET_CHIPS: &[&str] = &[
+  "Asset",
+  "Settlement",
+  "Portfolio",
+  "Identity",
+  "Balances",
+  "Staking",
+];
+/// Deepest reorg searched for a common ancestor.
+const MAX_REORG_DEPTH: u32 = 256;
+/// Number of blocks loaded at a time when following the chain from a chosen block.
+const RANGE_CHUNK: u32 = 50;
+/// Minimum widths of the block details tables, narrower panels use plain rows.
+const BLOCK_HEADER_MIN_WIDTH: f32 = 100.0 + 160.0 + 5.0 * 60.0;
+const EVENTS_TABLE_MIN_WIDTH: f32 = 60.0 + 60.0 + 100.0;
+/// Events shown in the block details until the user asks for all of them.
+const MAX_SHOWN_EVENTS: usize = 1000;
+/// Visited blocks kept in the block details breadcrumb.
+const MAX_BREADCRUMB: usize = 12;
+/// Number of chips added for the most frequent pallets in the events feed.
+const FREQUENT_PALLET_CHIPS: usize = 4;
 
 #[cfg(target_arch = "wasm32")]
 const PRELOAD_BLOCKS: u32 = 20;
@@ -20,9 +143,32 @@ const PRELOAD_BLOCKS: u32 = 200;
 pub struct BlockEventSummary {
   pub block: BlockNumber,
   pub number: u32,
-  pub name: &'static str,
+  pub name: EventName,
   /// Count the number of events in the block with the same type.
   pub count: u32,
+  /// Time of the block the event was emitted in.
+  pub timestamp: Option<u64>,
+}
+
+/// Backend connection status.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConnectionStatus {
+  #[default]
+  Connecting,
+  Connected,
+  Reconnecting,
+  Disconnected,
+}
+
+impl std::fmt::Display for ConnectionStatus {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Connecting => write!(f, "Connecting"),
+      Self::Connected => write!(f, "Connected"),
+      Self::Reconnecting => write!(f, "Reconnecting"),
+      Self::Disconnected => write!(f, "Disconnected"),
+    }
+  }
 }
 
 /// Backend chain state.
@@ -31,82 +177,726 @@ pub struct BlockEventSummary {
 pub struct BackendState {
   open: bool,
   url: String,
+  /// Archive node used for blocks that the main node has pruned.
+  archive_url: String,
+  settings: Settings,
+  /// Pause loading historical blocks, live blocks are still loaded.
+  preload_paused: bool,
+  /// File used to record and replay sessions (native only).
+  session_file: String,
+  /// Replay speed multiplier.
+  replay_speed: f64,
+  /// Pinned blocks, kept cached when older blocks are trimmed.
+  pinned_blocks: BTreeSet<BlockHash>,
+  /// Pinned events, by block hash and event index.
+  pinned_events: BTreeSet<(BlockHash, u32)>,
+  /// Bookmarked views, e.g. accounts, in the order they were added.
+  bookmarks: Vec<Bookmark>,
+  /// Index of the bookmark being renamed.
+  #[serde(skip)]
+  editing_bookmark: Option<usize>,
+
+  /// Auth token for gated nodes.  Not saved, it has to be entered again after a restart.
+  #[serde(skip)]
+  auth_token: String,
+  /// Node url of the last connect, restored if the user cancels a reconnect.
+  #[serde(skip)]
+  connected_url: String,
+  /// Waiting for the user to confirm a reconnect to a different node.
+  #[serde(skip)]
+  confirm_reconnect: bool,
 
   #[serde(skip)]
   need_save: bool,
 
+  #[serde(skip)]
+  status: ConnectionStatus,
+
+  #[serde(skip)]
+  stats: SessionStats,
+  #[serde(skip)]
+  block_rate: BlockRate,
+
   #[serde(skip)]
   genesis_hash: Option<BlockHash>,
+  /// The node's genesis hash isn't the one of the well-known network it was picked as.
+  #[serde(skip)]
+  genesis_mismatch: Option<String>,
+  /// SS58 prefix from the chain properties.
+  #[serde(skip)]
+  chain_ss58_prefix: Option<u16>,
+  /// Why the backend stopped decoding events.
+  #[serde(skip)]
+  decoding_error: Option<String>,
 
   #[serde(skip)]
   preload_blocks: u32,
   #[serde(skip)]
   preload_next: Option<BlockHash>,
+  /// `preload_next` still needs to be requested, because preloading was paused.
+  #[serde(skip)]
+  preload_stalled: bool,
 
   #[serde(skip)]
   best_block: BlockNumber,
+  /// Highest block number announced by `NewHeader`, `best_block` lags it while the UI
+  /// catches up.
+  #[serde(skip)]
+  best_header: BlockNumber,
+  #[serde(skip)]
+  best_hash: Option<BlockHash>,
+  /// Which heads the backend follows, and if it polls for them.
+  #[serde(skip)]
+  following: Option<(Follow, bool)>,
+  /// Parent hashes of the cached blocks, including blocks replaced by a reorg.  Used to find
+  /// the common ancestor of a reorg.
+  #[serde(skip)]
+  parents: HashMap<BlockHash, (BlockNumber, BlockHash)>,
+  /// Chain tip blocks announced by `NewHeader` that are still loading.  Only these can become
+  /// the best block, not historical blocks loaded on request.
+  #[serde(skip)]
+  pending_tips: HashSet<BlockHash>,
 
   #[serde(skip)]
   hash_to_number: HashMap<BlockHash, BlockNumber>,
+  /// Blocks whose events were counted and added to the feed, so a block arriving again (e.g.
+  /// preloaded and then announced) isn't counted twice.
+  #[serde(skip)]
+  counted_blocks: HashSet<BlockHash>,
   #[serde(skip)]
   blocks: HashMap<BlockNumber, BlockInfo>,
   #[serde(skip)]
   recent_blocks: VecDeque<BlockNumber>,
+  /// Blocks shown by the open views this frame, see `keep_blocks`.
+  #[serde(skip)]
+  resident_blocks: Vec<Range<BlockNumber>>,
+  #[serde(skip)]
+  call_index: CallIndex,
   #[serde(skip)]
   recent_events: VecDeque<BlockEventSummary>,
+  /// Frames in a row that ran out of update budget.
+  #[serde(skip)]
+  lagging_frames: u32,
+  /// Blocks whose events were counted but left out of the events feed to catch up.
+  #[serde(skip)]
+  summarized_blocks: u64,
+  #[serde(skip)]
+  justifications: HashMap<BlockHash, Option<JustificationInfo>>,
+  #[serde(skip)]
+  accounts: HashMap<AccountKey, Option<AccountBalance>>,
+  /// Raw RPC responses captured while RPC debugging is on.
+  #[serde(skip)]
+  raw_rpc: HashMap<RpcSubject, Vec<RawRpcCall>>,
+  /// Step by step decoding of blocks' events, for debugging.
+  #[serde(skip)]
+  event_traces: HashMap<BlockHash, Result<Vec<EventTrace>, String>>,
+  /// Pallet constants of the current runtime, `None` while loading.
+  #[serde(skip)]
+  constants: Option<Result<Vec<PalletConstants>, String>>,
+  /// Saving the runtime metadata: `None` while loading, then where it was saved or the error.
+  #[serde(skip)]
+  metadata_export: Option<Option<Result<String, String>>>,
+  /// Full values of elided events, `None` if loading failed.
+  #[serde(skip)]
+  event_values: HashMap<(BlockHash, u32), Option<serde_json::Value>>,
+  /// `FindExtrinsic` results: block hash and extrinsic index.
+  #[serde(skip)]
+  found_extrinsics: HashMap<TxHash, Option<(BlockHash, u32)>>,
+  /// `FindBlockByTime` results.
   #[serde(skip)]
-  backend: Backend,
+  blocks_at_time: HashMap<u64, Option<BlockHash>>,
+  /// Latest `GetChainStatus` result.
+  #[serde(skip)]
+  chain_status: Option<ChainStatus>,
+  /// When `chain_status` was last requested, in `egui` input time.
+  #[serde(skip)]
+  chain_status_requested: Option<f64>,
+  /// `GetBlockByNumber` requests the node had no block for, forgotten when a newer best block
+  /// arrives.
+  #[serde(skip)]
+  missing_blocks: HashSet<BlockNumber>,
+  /// Recent errors, newest first, with their time in milliseconds since the unix epoch.
+  #[serde(skip)]
+  error_log: VecDeque<(u64, String)>,
+  #[serde(skip)]
+  recorder: Option<Recorder>,
+  /// The backend is a `ReplayBackend`.
+  #[serde(skip)]
+  replaying: bool,
+  /// Pasted recording to replay (web only).
+  #[serde(skip)]
+  replay_input: String,
+  #[serde(skip)]
+  replay_error: Option<String>,
+  /// Cached result of the last update check.
+  #[cfg(not(target_arch = "wasm32"))]
+  update_check: UpdateCheck,
+  #[cfg(not(target_arch = "wasm32"))]
+  #[serde(skip)]
+  forwarder: Option<EventForwarder>,
+  /// Endpoint and format the forwarder was started with.
+  #[cfg(not(target_arch = "wasm32"))]
+  #[serde(skip)]
+  forward_target: Option<(String, ForwardFormat)>,
+  #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+  #[serde(skip)]
+  metrics: Option<MetricsServer>,
+  /// Port the metrics server was last started on, so a failed start isn't retried every frame.
+  #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+  #[serde(skip)]
+  metrics_port: Option<u16>,
+  #[serde(skip)]
+  backend: Box<dyn BackendApi>,
 }
 
 impl Default for BackendState {
   fn default() -> Self {
+    Self::with_backend(Backend::new())
+  }
+}
+
+impl BackendState {
+  /// Create the chain state using a custom backend, e.g. a `MockBackend`.
+  pub fn with_backend(backend: impl BackendApi + 'static) -> Self {
     Self {
       open: true,
       need_save: true,
       url: POLYMESH_TESTNET.to_owned(),
+      archive_url: "".into(),
+      auth_token: Default::default(),
+      connected_url: Default::default(),
+      confirm_reconnect: false,
+      settings: Default::default(),
+      preload_paused: false,
+      pinned_blocks: Default::default(),
+      pinned_events: Default::default(),
+      bookmarks: Default::default(),
+      editing_bookmark: None,
+      session_file: "session.jsonl".into(),
+      replay_speed: 1.0,
+      status: Default::default(),
+      stats: Default::default(),
+      block_rate: Default::default(),
       genesis_hash: None,
+      genesis_mismatch: None,
+      chain_ss58_prefix: None,
+      decoding_error: None,
       best_block: 0,
+      best_header: 0,
+      best_hash: None,
+      following: None,
+      parents: Default::default(),
+      pending_tips: Default::default(),
       preload_blocks: PRELOAD_BLOCKS,
       preload_next: None,
+      preload_stalled: false,
 
       hash_to_number: Default::default(),
+      counted_blocks: Default::default(),
       blocks: Default::default(),
       recent_blocks: Default::default(),
+      resident_blocks: Default::default(),
       recent_events: Default::default(),
-      backend: Backend::new(),
+      lagging_frames: 0,
+      summarized_blocks: 0,
+      call_index: Default::default(),
+      justifications: Default::default(),
+      accounts: Default::default(),
+      raw_rpc: Default::default(),
+      event_traces: Default::default(),
+      constants: None,
+      metadata_export: None,
+      event_values: Default::default(),
+      found_extrinsics: Default::default(),
+      blocks_at_time: Default::default(),
+      chain_status: None,
+      chain_status_requested: None,
+      missing_blocks: Default::default(),
+      error_log: Default::default(),
+      recorder: None,
+      replaying: false,
+      replay_input: Default::default(),
+      replay_error: None,
+      #[cfg(not(target_arch = "wasm32"))]
+      update_check: Default::default(),
+      #[cfg(not(target_arch = "wasm32"))]
+      forwarder: None,
+      #[cfg(not(target_arch = "wasm32"))]
+      forward_target: None,
+      #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+      metrics: None,
+      #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+      metrics_port: None,
+      backend: Box::new(backend),
     }
   }
-}
 
-impl BackendState {
   fn clear(&mut self) {
+    self.stats = Default::default();
     self.genesis_hash = None;
+    self.genesis_mismatch = None;
+    self.chain_ss58_prefix = None;
+    self.decoding_error = None;
     self.best_block = 0;
+    self.best_header = 0;
+    self.best_hash = None;
+    self.parents.clear();
+    self.pending_tips.clear();
     self.preload_blocks = PRELOAD_BLOCKS;
     self.preload_next = None;
+    self.preload_stalled = false;
 
     self.hash_to_number.clear();
+    self.counted_blocks.clear();
     self.blocks.clear();
     self.recent_blocks.clear();
     self.recent_events.clear();
+    self.summarized_blocks = 0;
+    self.call_index.clear();
+    self.justifications.clear();
+    self.accounts.clear();
+    self.raw_rpc.clear();
+    self.event_traces.clear();
+    self.constants = None;
+    self.event_values.clear();
+    self.found_extrinsics.clear();
+    self.blocks_at_time.clear();
+    self.missing_blocks.clear();
+    self.chain_status = None;
+    self.chain_status_requested = None;
+  }
+
+  /// Node url with the auth token.
+  fn connect_url(&self) -> String {
+    with_auth_token(&self.url, &self.auth_token)
   }
 
   fn connect(&mut self) {
-    if let Err(err) = self.backend.connect_to(&self.url) {
+    self.status = ConnectionStatus::Connecting;
+    self.connected_url = self.url.clone();
+    self.confirm_reconnect = false;
+    if let Err(err) = self
+      .backend
+      .set_connect_timeout(self.settings.connect_timeout())
+    {
+      log::error!("Failed to send connect timeout reqest to backend: {err:?}");
+    }
+    if let Err(err) = self.backend.set_follow(self.settings.follow) {
+      log::error!("Failed to send follow reqest to backend: {err:?}");
+    }
+    if let Err(err) = self.backend.connect_to(&self.connect_url()) {
       log::error!("Failed to send ConnectTo reqest to backend: {err:?}");
     }
   }
 
+  /// What a switch to another chain would clear, empty if nothing worth asking about.
+  fn reconnect_losses(&self) -> Vec<String> {
+    let mut losses = Vec::new();
+    let pinned = self
+      .blocks
+      .values()
+      .filter(|block| self.is_block_pinned(&block.hash))
+      .count();
+    if pinned > 0 {
+      losses.push(format!(
+        "{pinned} pinned blocks (the pins are kept, the blocks are reloaded if the chain is the same)"
+      ));
+    }
+    // Only history beyond the blocks preloaded on connect.
+    if self.recent_blocks.len() > PRELOAD_BLOCKS as usize {
+      losses.push(format!("{} loaded blocks", self.recent_blocks.len()));
+    }
+    if !self.accounts.is_empty() {
+      losses.push(format!("{} looked up accounts", self.accounts.len()));
+    }
+    losses
+  }
+
+  /// Connect to the node url, asking the user first if the new node could be another chain and
+  /// clear the loaded state.
+  fn reconnect(&mut self) {
+    if self.connected_url != self.url && !self.reconnect_losses().is_empty() {
+      self.confirm_reconnect = true;
+    } else {
+      self.connect();
+    }
+  }
+
+  fn confirm_reconnect_ui(&mut self, ctx: &egui::Context) {
+    if !self.confirm_reconnect {
+      return;
+    }
+    let mut confirmed = None;
+    egui::Window::new("Switch node?")
+      .collapsible(false)
+      .resizable(false)
+      .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+      .show(ctx, |ui| {
+        ui.label(format!("Connect to {}?", redact_url(&self.url)));
+        ui.label("If the node is on a different chain, this will be cleared:");
+        for loss in self.reconnect_losses() {
+          ui.label(format!("• {loss}"));
+        }
+        ui.weak("Filters and pins are kept.");
+        ui.horizontal(|ui| {
+          if ui.button("Connect").clicked() {
+            confirmed = Some(true);
+          }
+          if ui.button("Cancel").clicked() {
+            confirmed = Some(false);
+          }
+        });
+      });
+    match confirmed {
+      Some(true) => self.connect(),
+      Some(false) => {
+        self.url = self.connected_url.clone();
+        self.confirm_reconnect = false;
+        self.need_save = true;
+      }
+      None => (),
+    }
+  }
+
+  fn disconnect(&mut self) {
+    self.status = ConnectionStatus::Disconnected;
+    if let Err(err) = self.backend.disconnect() {
+      log::error!("Failed to send disconnect reqest to backend: {err:?}");
+    }
+  }
+
+  /// Ask the backend to decode events again and reload the cached blocks without events.
+  fn retry_decoding(&mut self) {
+    if let Err(err) = self.backend.retry_decoding() {
+      log::error!("Failed to send retry decoding reqest to backend: {err:?}");
+      return;
+    }
+    self.decoding_error = None;
+    for block in self.blocks.values() {
+      if block.events_error.is_some() {
+        self.get_block_info(block.hash);
+      }
+    }
+  }
+
+  /// Add an error to the error log.
+  pub fn push_error(&mut self, msg: String) {
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    self.error_log.push_front((now, msg));
+    self.error_log.truncate(MAX_ERROR_LOG);
+  }
+
+  fn error_log_ui(&mut self, ui: &mut egui::Ui) {
+    if ui.button("Clear").clicked() {
+      self.error_log.clear();
+    }
+    ScrollArea::vertical()
+      .id_source("error_log")
+      .max_height(200.0)
+      .show(ui, |ui| {
+        for (time, msg) in &self.error_log {
+          ui.horizontal_wrapped(|ui| {
+            ui.weak(format_time(*time, self.settings.time_zone));
+            ui.label(msg);
+          });
+        }
+      });
+  }
+
+  fn start_recording(&mut self) {
+    #[cfg(not(target_arch = "wasm32"))]
+    let recorder = Recorder::to_file(&self.session_file);
+    #[cfg(target_arch = "wasm32")]
+    let recorder = Ok(Recorder::in_memory());
+    match recorder {
+      Ok(recorder) => {
+        self.recorder = Some(recorder);
+        self.replay_error = None;
+      }
+      Err(err) => {
+        self.replay_error = Some(format!("Failed to start recording: {err}"));
+      }
+    }
+  }
+
+  /// Stop recording.  Returns the recording if it was kept in memory.
+  fn stop_recording(&mut self) -> Option<String> {
+    let recorder = self.recorder.take()?;
+    let recording = recorder.recording()?;
+    Some(String::from_utf8_lossy(recording).into_owned())
+  }
+
+  fn start_replay(&mut self) {
+    #[cfg(not(target_arch = "wasm32"))]
+    let recording = std::fs::read_to_string(&self.session_file).map_err(|e| e.to_string());
+    #[cfg(target_arch = "wasm32")]
+    let recording = Ok::<_, String>(self.replay_input.clone());
+    match recording.and_then(|recording| ReplayBackend::parse(&recording, self.replay_speed)) {
+      Ok(replay) => {
+        log::info!("Replay session.");
+        self.clear();
+        self.backend = Box::new(replay);
+        self.status = ConnectionStatus::Connecting;
+        self.replaying = true;
+        self.replay_error = None;
+      }
+      Err(err) => {
+        self.replay_error = Some(format!("Failed to load recording: {err}"));
+      }
+    }
+  }
+
+  fn stop_replay(&mut self) {
+    self.clear();
+    self.backend = Box::new(Backend::new());
+    self.replaying = false;
+    self.connect();
+  }
+
+  fn record_replay_ui(&mut self, ui: &mut egui::Ui) {
+    #[cfg(not(target_arch = "wasm32"))]
+    ui.horizontal(|ui| {
+      ui.label("Session file: ");
+      if ui.text_edit_singleline(&mut self.session_file).changed() {
+        self.need_save = true;
+      }
+    });
+    ui.horizontal(|ui| match &self.recorder {
+      Some(recorder) => {
+        ui.label(format!("Recording: {} events", recorder.events()));
+        let button = ui.button("⏹ Stop recording");
+        let mut copied = false;
+        if button.clicked() {
+          if let Some(recording) = self.stop_recording() {
+            // No file access on the web, hand the recording over via the clipboard.
+            ui.output_mut(|o| o.copied_text = recording);
+            log::info!("Recording copied to the clipboard.");
+            copied = true;
+          }
+        }
+        copied_ui(ui, &button, copied);
+      }
+      None => {
+        if ui
+          .add_enabled(!self.replaying, Button::new("⏺ Record"))
+          .clicked()
+        {
+          self.start_recording();
+        }
+      }
+    });
+    #[cfg(target_arch = "wasm32")]
+    if !self.replaying {
+      ui.add(
+        TextEdit::multiline(&mut self.replay_input)
+          .hint_text("Paste a recording to replay")
+          .desired_rows(2),
+      );
+    }
+    ui.horizontal(|ui| {
+      if self.replaying {
+        ui.label("Replaying");
+        if ui.button("⏹ Stop replay").clicked() {
+          self.stop_replay();
+        }
+      } else {
+        if ui
+          .add_enabled(self.recorder.is_none(), Button::new("▶ Replay"))
+          .clicked()
+        {
+          self.start_replay();
+        }
+        ui.label("Speed: ");
+        if ui
+          .add(
+            DragValue::new(&mut self.replay_speed)
+              .clamp_range(0.1..=100.0)
+              .speed(0.1)
+              .suffix("x"),
+          )
+          .changed()
+        {
+          self.need_save = true;
+        }
+      }
+    });
+    if let Some(err) = &self.replay_error {
+      ui.colored_label(ui.visuals().error_fg_color, err);
+    }
+  }
+
+  /// Banner shown while event decoding is disabled.
+  fn decoding_banner_ui(&mut self, ctx: &egui::Context) {
+    let Some(err) = &self.decoding_error else {
+      return;
+    };
+    let mut retry = false;
+    egui::TopBottomPanel::top("decoding_banner").show(ctx, |ui| {
+      ui.horizontal(|ui| {
+        ui.colored_label(
+          ui.visuals().warn_fg_color,
+          "⚠ Events unavailable or incompatible — event decoding disabled.  Headers and hashes are \
+           still shown.",
+        )
+        .on_hover_text(err);
+        retry = ui.button("Retry metadata").clicked();
+      });
+    });
+    if retry {
+      self.retry_decoding();
+    }
+  }
+
+  fn find_extrinsic(&mut self, hash: TxHash) {
+    // Forget the result of an earlier search.
+    self.found_extrinsics.remove(&hash);
+    if let Err(err) = self.backend.find_extrinsic(hash) {
+      log::error!("Failed to send find extrinsic reqest to backend: {err:?}");
+    }
+  }
+
+  /// Ask for a fresh `ChainStatus` every `CHAIN_STATUS_INTERVAL`, views showing it call this
+  /// every frame.
+  pub fn refresh_chain_status(&mut self, now: f64) {
+    if self.status != ConnectionStatus::Connected
+      || self
+        .chain_status_requested
+        .is_some_and(|requested| now - requested < CHAIN_STATUS_INTERVAL)
+    {
+      return;
+    }
+    self.chain_status_requested = Some(now);
+    if let Err(err) = self.backend.get_chain_status() {
+      log::error!("Failed to send chain status reqest to backend: {err:?}");
+    }
+  }
+
+  fn find_block_by_time(&mut self, time: u64) {
+    // Forget the result of an earlier search.
+    self.blocks_at_time.remove(&time);
+    if let Err(err) = self.backend.find_block_by_time(time) {
+      log::error!("Failed to send find block by time reqest to backend: {err:?}");
+    }
+  }
+
+  fn get_block_range(&self, start: BlockNumber, count: u32) {
+    if let Err(err) = self.backend.get_block_range(start, count) {
+      log::error!("Failed to send block range reqest to backend: {err:?}");
+    }
+  }
+
+  fn cancel_block_range(&self) {
+    if let Err(err) = self.backend.cancel_block_range() {
+      log::error!("Failed to send cancel block range reqest to backend: {err:?}");
+    }
+  }
+
   fn get_block_info(&self, hash: BlockHash) {
     if let Err(err) = self.backend.get_block_info(hash) {
       log::error!("Failed to send block info reqest to backend: {err:?}");
     }
   }
 
+  fn preload_block(&self, hash: BlockHash) {
+    if let Err(err) = self.backend.preload_block(hash) {
+      log::error!("Failed to send preload block reqest to backend: {err:?}");
+    }
+  }
+
+  fn get_block_by_number(&self, number: BlockNumber) {
+    if let Err(err) = self.backend.get_block_by_number(number) {
+      log::error!("Failed to send block by number reqest to backend: {err:?}");
+    }
+  }
+
+  fn get_justification(&self, hash: BlockHash) {
+    if let Err(err) = self.backend.get_justification(hash) {
+      log::error!("Failed to send justification reqest to backend: {err:?}");
+    }
+  }
+
+  fn trace_events(&self, block: &BlockInfo) {
+    if let Err(err) = self.backend.trace_events(block.hash, block.number()) {
+      log::error!("Failed to send trace events reqest to backend: {err:?}");
+    }
+  }
+
+  fn get_account_balance(&self, account: AccountKey) {
+    if let Err(err) = self.backend.get_account_balance(account) {
+      log::error!("Failed to send account balance reqest to backend: {err:?}");
+    }
+  }
+
   fn check_node_url(&mut self) {
-    if self.backend.get_url() != self.url {
+    // Stay disconnected until the user connects again.
+    if self.status == ConnectionStatus::Disconnected {
+      return;
+    }
+    if self.backend.get_url() != self.connect_url() {
       log::info!("Node url changed.  Reconnect to backend.");
-      self.connect();
+      self.reconnect();
+    }
+    if self.status == ConnectionStatus::Connected
+      && self.backend.get_archive_url() != self.archive_url
+    {
+      self.set_archive();
+    }
+  }
+
+  fn set_poll_interval(&self) {
+    if let Err(err) = self
+      .backend
+      .set_poll_interval(self.settings.poll_interval())
+    {
+      log::error!("Failed to send poll interval reqest to backend: {err:?}");
+    }
+  }
+
+  fn set_debug_rpc(&self) {
+    if let Err(err) = self.backend.set_debug_rpc(self.settings.debug_rpc) {
+      log::error!("Failed to send debug RPC reqest to backend: {err:?}");
+    }
+  }
+
+  fn set_max_event_value(&self) {
+    if let Err(err) = self
+      .backend
+      .set_max_event_value(self.settings.max_event_value())
+    {
+      log::error!("Failed to send max event value reqest to backend: {err:?}");
+    }
+  }
+
+  fn get_constants(&self) {
+    if let Err(err) = self.backend.get_constants() {
+      log::error!("Failed to send constants reqest to backend: {err:?}");
+    }
+  }
+
+  /// Save the raw runtime metadata as `metadata.scale`, once the backend loaded it.
+  fn export_metadata(&mut self) {
+    if let Err(err) = self.backend.get_raw_metadata() {
+      log::error!("Failed to send raw metadata reqest to backend: {err:?}");
+      return;
+    }
+    self.metadata_export = Some(None);
+  }
+
+  fn get_event_value(&self, hash: BlockHash, index: u32) {
+    if let Err(err) = self.backend.get_event_value(hash, index) {
+      log::error!("Failed to send event value reqest to backend: {err:?}");
+    }
+  }
+
+  fn set_lazy_events(&self) {
+    if let Err(err) = self.backend.set_lazy_events(self.settings.lazy_events) {
+      log::error!("Failed to send lazy events reqest to backend: {err:?}");
+    }
+  }
+
+  fn set_archive(&mut self) {
+    if let Err(err) = self.backend.set_archive(&self.archive_url) {
+      log::error!("Failed to send archive node reqest to backend: {err:?}");
     }
   }
 
@@ -121,30 +911,91 @@ impl BackendState {
     }
   }
 
-  fn next_preload(&mut self, block: &BlockInfo) {
+  fn next_preload(&mut self, block: &BlockInfo, is_tip: bool) {
     // Check if we are still preloading.
     if self.preload_blocks == 0 {
       return;
     }
-    // Make sure it was our last requested block.
-    if let Some(next) = &self.preload_next {
-      if next != &block.hash {
-        return;
-      }
+    // Make sure it was our last requested block, or start from the chain tip.
+    match &self.preload_next {
+      Some(next) if next != &block.hash => return,
+      None if !is_tip => return,
+      _ => (),
     }
     // Preload parent block.
-    self.preload_blocks -= 1;
     let hash = block.header.parent_hash;
     self.preload_next = Some(hash);
-    if let Err(err) = self.backend.get_block_info(hash) {
-      log::error!("Backend error: {err:?}");
+    if self.preload_paused {
+      // Continue from the parent block when preloading is resumed.
+      self.preload_stalled = true;
+      return;
+    }
+    self.request_preload(hash);
+  }
+
+  fn request_preload(&mut self, hash: BlockHash) {
+    self.preload_blocks -= 1;
+    self.preload_block(hash);
+  }
+
+  pub fn set_preload_paused(&mut self, paused: bool) {
+    self.preload_paused = paused;
+    self.need_save = true;
+    if !paused && self.preload_stalled {
+      self.preload_stalled = false;
+      if let Some(hash) = self.preload_next {
+        self.request_preload(hash);
+      }
     }
   }
 
-  pub fn backend_updates(&mut self) {
-    // Poll the backend for updates.
-    for _ in 0..MAX_BACKEND_UPDATES {
-      match self.backend.next_update() {
+  /// Oldest cached block, the next history batch starts from its parent.
+  fn oldest_block(&self) -> Option<&BlockInfo> {
+    self
+      .recent_blocks
+      .iter()
+      .min()
+      .and_then(|number| self.blocks.get(number))
+  }
+
+  pub fn can_load_older_blocks(&self) -> bool {
+    self.preload_blocks == 0 && self.oldest_block().is_some_and(|block| block.number() > 0)
+  }
+
+  /// Re-arm preloading for another batch of blocks older than the oldest cached block.
+  pub fn load_older_blocks(&mut self) {
+    let hash = match self.oldest_block() {
+      Some(block) if block.number() > 0 => block.header.parent_hash,
+      _ => return,
+    };
+    self.preload_blocks = PRELOAD_BLOCKS;
+    self.preload_next = Some(hash);
+    self.preload_block(hash);
+  }
+
+  /// Poll the backend for updates until the channel is empty or the frame's update budget is
+  /// used.  Returns the number of updates processed.
+  pub fn backend_updates(&mut self) -> usize {
+    self.backend_updates_within(UpdateBudget::start(self.settings.update_budget()))
+  }
+
+  fn backend_updates_within(&mut self, budget: UpdateBudget) -> usize {
+    // On fast chains the updates can arrive faster than the budget can apply them.  After
+    // lagging for a while, the blocks are summarized to catch up, keeping only their counts.
+    let catching_up = self.lagging_frames >= LAGGING_FRAMES;
+    let mut processed = 0;
+    loop {
+      // At least one update is applied per frame, so catching up always progresses.
+      if processed > 0 && budget.exceeded() {
+        self.lagging_frames += 1;
+        return processed;
+      }
+      processed += 1;
+      let event = self.backend.next_update();
+      if let (Some(recorder), Some(event)) = (&mut self.recorder, &event) {
+        recorder.record(event);
+      }
+      match event {
         Some(BackendEvent::Connected {
           genesis,
           is_reconnect,
@@ -156,121 +1007,677 @@ impl BackendState {
               log::info!("---- Different genesis hash clear chain state.");
               // Clear old chain data.
               self.clear();
+            } else {
+              self.stats.reconnects += 1;
             }
           }
           self.genesis_hash = Some(genesis);
+          self.genesis_mismatch = self.check_genesis(genesis);
+          if let Some(err) = &self.genesis_mismatch {
+            log::warn!("{err}");
+            self.push_error(err.clone());
+          }
+          self.status = ConnectionStatus::Connected;
+          // The backend decodes events again after connecting.
+          self.decoding_error = None;
+          if !self.archive_url.is_empty() {
+            self.set_archive();
+          }
+          self.set_poll_interval();
+          self.set_debug_rpc();
+          self.set_lazy_events();
+          self.set_max_event_value();
+          // The node might run another runtime.
+          self.constants = None;
+          self.get_constants();
+        }
+        Some(BackendEvent::SubscriptionEnded) => {
+          log::warn!("Block subscription ended.  Reconnect to backend.");
+          self.connect();
+          self.status = ConnectionStatus::Reconnecting;
         }
         Some(BackendEvent::NewHeader(header)) => {
-          // New block header.  Request block info.
-          if let Err(err) = self.backend.get_block_info(header.hash()) {
+          // New block header.  Request block info, unless it is already cached, e.g. after
+          // reconnecting to another node of the same chain or from preloading.  A cached block
+          // still becomes the tip.
+          let hash = header.hash();
+          self.best_header = self.best_header.max(header.number);
+          self.pending_tips.insert(hash);
+          if let Some(block) = self.block_by_hash(&hash) {
+            self.add_block(block.clone(), catching_up);
+            continue;
+          }
+          if let Err(err) = self.backend.get_block_info(hash) {
             log::error!("Backend error: {err:?}");
           }
         }
+        Some(BackendEvent::EventValue { hash, index, value }) => {
+          self.event_values.insert((hash, index), value);
+        }
+        Some(BackendEvent::EventTraces { hash, traces }) => {
+          self.event_traces.insert(hash, traces);
+        }
+        Some(BackendEvent::Constants(constants)) => {
+          self.constants = Some(constants);
+        }
+        Some(BackendEvent::RawMetadata(metadata)) => {
+          let res = metadata.and_then(|metadata| save_file("metadata.scale", &metadata));
+          if let Err(err) = &res {
+            self.push_error(format!("Failed to save metadata: {err}"));
+          }
+          self.metadata_export = Some(Some(res));
+        }
+        Some(BackendEvent::Following { follow, polling }) => {
+          self.following = Some((follow, polling));
+        }
+        Some(BackendEvent::Justification {
+          hash,
+          justification,
+        }) => {
+          self.justifications.insert(hash, justification);
+        }
+        Some(BackendEvent::ExtrinsicFound { hash, location }) => {
+          self.found_extrinsics.insert(hash, location);
+        }
+        Some(BackendEvent::BlockAtTime { time, hash }) => {
+          self.blocks_at_time.insert(time, hash);
+        }
+        Some(BackendEvent::ChainStatus(status)) => {
+          self.chain_status = Some(status);
+        }
+        Some(BackendEvent::BlockNotFound(number)) => {
+          self.missing_blocks.insert(number);
+        }
+        Some(BackendEvent::DecodingDisabled(err)) => {
+          self.push_error(format!("Event decoding disabled: {err}"));
+          self.decoding_error = Some(err);
+        }
+        Some(BackendEvent::ConnectionError(err)) => {
+          // Let the user retry with the Connect button.
+          self.status = ConnectionStatus::Disconnected;
+          self.push_error(err);
+        }
+        Some(BackendEvent::Error(err)) => {
+          self.push_error(err);
+        }
+        Some(BackendEvent::ChainProperties { ss58_format }) => {
+          self.chain_ss58_prefix = ss58_format;
+        }
+        Some(BackendEvent::AccountBalance { account, balance }) => {
+          self.accounts.insert(account, balance);
+        }
+        Some(BackendEvent::RawRpc { subject, calls }) => {
+          self.raw_rpc.insert(subject, calls);
+        }
         Some(BackendEvent::BlockInfo(block)) => {
-          // Check if the block is the newest best.
-          let number = block.number();
-          let is_best = number > self.best_block;
-          if is_best {
-            self.best_block = number;
+          self.add_block(block, catching_up);
+        }
+        None => {
+          // Channel is empty.
+          self.lagging_frames = 0;
+          return processed - 1;
+        }
+      }
+    }
+  }
+
+  /// Cache a block from the backend, and count its events.  Summarized when `catching_up`.
+  fn add_block(&mut self, mut block: BlockInfo, catching_up: bool) {
+    // Check if the block is the newest best.
+    let number = block.number();
+    let is_tip = self.pending_tips.remove(&block.hash);
+    let is_best = is_tip && number > self.best_block;
+    self
+      .parents
+      .insert(block.hash, (number, block.header.parent_hash));
+    if is_best {
+      self.check_reorg(&block);
+      self.best_block = number;
+      self.best_hash = Some(block.hash);
+      // The blocks up to the best block exist now.
+      self.missing_blocks.retain(|missing| *missing > number);
+    }
+
+    // Handle preloading.
+    self.next_preload(&block, is_tip);
+    self.block_rate.add_block();
+    // The same block can arrive twice, e.g. from preloading and as a new head.  Its
+    // events are counted and added to the feed once, header only copies have none.
+    let cached = self
+      .blocks
+      .get(&number)
+      .filter(|cached| cached.hash == block.hash);
+    if cached.is_none() {
+      self.stats.add_block();
+    }
+    // A header only copy (e.g. a lazy preload) doesn't replace the cached full block.
+    let keep_cached = block.header_only && cached.is_some_and(|cached| !cached.header_only);
+    let duplicate = block.header_only || self.counted_blocks.contains(&block.hash);
+    if !duplicate {
+      self.counted_blocks.insert(block.hash);
+      self.block_events_added(&block);
+    }
+    // A block loaded while decoding was disabled is indexed when it's reloaded with its calls.
+    if !block.header_only {
+      self.call_index.add_block(&block);
+    }
+    // Keep the counts of summarized blocks, but leave their events out of the feed.  The
+    // best block's events are still shown.
+    let summarize = catching_up && !is_best && !duplicate;
+    if summarize {
+      self.summarized_blocks += 1;
+    }
+
+    // Update recent events.
+    let events = if duplicate || summarize {
+      &[][..]
+    } else {
+      &block.events[..]
+    };
+    events
+      .iter()
+      .fold(
+        HashMap::new(),
+        |mut events: HashMap<(_, _), BlockEventSummary>, event| {
+          use std::collections::hash_map::Entry;
+          // Ignore some common events.
+          if event.name.starts_with("System.") {
+            return events;
           }
 
-          // Handle preloading.
-          self.next_preload(&block);
+          let key = (event.block, &event.name);
+          match events.entry(key) {
+            Entry::Occupied(entry) => {
+              // Duplicate event type, just bump the count.
+              entry.into_mut().count += 1;
+            }
+            Entry::Vacant(entry) => {
+              // New event type.
+              entry.insert(BlockEventSummary {
+                block: event.block,
+                number: event.number,
+                name: event.name.clone(),
+                count: 1,
+                timestamp: block.timestamp,
+              });
+            }
+          }
 
-          // Update recent events.
-          block
-            .events
-            .iter()
-            .fold(
-              HashMap::new(),
-              |mut events: HashMap<(_, _), BlockEventSummary>, event| {
-                use std::collections::hash_map::Entry;
-                // Ignore some common events.
-                if event.name.starts_with("System.") {
-                  return events;
-                }
+          events
+        },
+      )
+      .into_iter()
+      .for_each(|(_, event)| {
+        if is_best {
+          self.recent_events.push_front(event);
+        } else {
+          self.recent_events.push_back(event);
+        }
+      });
+    if summarize {
+      // Cache the block header and counts only, opening it loads the events and extrinsics again.
+      block.summarize();
+    }
+    // Update blocks.
+    self.hash_to_number.insert(block.hash, number);
+    let is_new = !keep_cached && self.blocks.insert(number, block).is_none();
+    // Update recent blocks, a cached block that became the best moves to the front.
+    if is_best {
+      if !is_new {
+        self.recent_blocks.retain(|recent| *recent != number);
+      }
+      self.recent_blocks.push_front(number);
+    } else if is_new {
+      self.recent_blocks.push_back(number);
+    }
+    // Trim old events.
+    while self.recent_events.len() > MAX_RECENT_EVENTS {
+      self.recent_events.pop_back();
+    }
+  }
 
-                let key = (event.block, &event.name);
-                match events.entry(key) {
-                  Entry::Occupied(entry) => {
-                    // Duplicate event type, just bump the count.
-                    entry.into_mut().count += 1;
-                  }
-                  Entry::Vacant(entry) => {
-                    // New event type.
-                    entry.insert(BlockEventSummary {
-                      block: event.block,
-                      number: event.number,
-                      name: event.name,
-                      count: 1,
-                    });
-                  }
-                }
+  /// Keep these blocks cached while they are shown, e.g. a loaded block range.  Views call this
+  /// every frame.
+  pub fn keep_blocks(&mut self, numbers: Range<BlockNumber>) {
+    self.resident_blocks.push(numbers);
+  }
+
+  /// Trim the oldest blocks by number.  Pinned blocks stay cached for the pinned panel, and the
+  /// blocks shown by the open views stay cached.
+  fn trim_blocks(&mut self) {
+    let excess = self.recent_blocks.len().saturating_sub(MAX_RECENT_BLOCKS);
+    if excess == 0 {
+      return;
+    }
+    let mut numbers = self
+      .recent_blocks
+      .iter()
+      .copied()
+      .filter(|number| {
+        !self
+          .resident_blocks
+          .iter()
+          .any(|range| range.contains(number))
+          && !self
+            .blocks
+            .get(number)
+            .is_some_and(|block| self.is_block_pinned(&block.hash))
+      })
+      .collect::<Vec<_>>();
+    numbers.sort_unstable();
+    numbers.truncate(excess);
+    let trimmed = numbers.into_iter().collect::<HashSet<_>>();
+    self
+      .recent_blocks
+      .retain(|number| !trimmed.contains(number));
+    for number in trimmed {
+      if let Some(block) = self.blocks.remove(&number) {
+        self.hash_to_number.remove(&block.hash);
+        self.parents.remove(&block.hash);
+        self.counted_blocks.remove(&block.hash);
+        self.raw_rpc.remove(&RpcSubject::Block(block.hash));
+        self.event_traces.remove(&block.hash);
+        self.event_values.retain(|(hash, _), _| *hash != block.hash);
+      }
+    }
+  }
+
+  /// Count a newly loaded block's events, and forward them.
+  fn block_events_added(&mut self, block: &BlockInfo) {
+    self.stats.add_events(block);
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(forwarder) = &self.forwarder {
+      forwarder.push_block(block);
+    }
+    let decode_errors = block.events.iter().filter(|e| e.decode_error).count();
+    if decode_errors > 0 {
+      let number = block.number();
+      self.push_error(format!(
+        "Block #{number}: {decode_errors} events failed to decode"
+      ));
+    }
+  }
+
+  /// Check if a new best block replaced the old best block's chain, and log the reorg's depth.
+  fn check_reorg(&mut self, block: &BlockInfo) {
+    let Some(old_hash) = self.best_hash else {
+      return;
+    };
+    let old_number = self.best_block;
+    if block.header.parent_hash == old_hash {
+      return;
+    }
+    // Walk both chains back to their common ancestor.
+    let mut old = (old_number, old_hash);
+    let mut new = (block.number() - 1, block.header.parent_hash);
+    let step = |(_, hash): (BlockNumber, BlockHash)| {
+      self
+        .parents
+        .get(&hash)
+        .map(|(number, parent)| (number.saturating_sub(1), *parent))
+    };
+    for _ in 0..MAX_REORG_DEPTH * 2 {
+      if old.1 == new.1 {
+        let depth = old_number - old.0;
+        // The old best is an ancestor, the blocks between them arrived before the new tip.
+        if depth == 0 {
+          return;
+        }
+        log::warn!("Reorg at #{}: {depth} blocks replaced", old.0 + 1);
+        self.stats.add_reorg(depth);
+        self.push_error(format!(
+          "Reorg: {depth} blocks replaced after #{} ({:?})",
+          old.0, old.1
+        ));
+        return;
+      }
+      let (next_old, next_new) = if new.0 > old.0 {
+        (Some(old), step(new))
+      } else if old.0 > new.0 {
+        (step(old), Some(new))
+      } else {
+        (step(old), step(new))
+      };
+      // Missing blocks (e.g. a gap after reconnecting) aren't a reorg.
+      match (next_old, next_new) {
+        (Some(next_old), Some(next_new)) => {
+          old = next_old;
+          new = next_new;
+        }
+        _ => return,
+      }
+    }
+  }
+
+  pub fn genesis_hash(&self) -> Option<BlockHash> {
+    self.genesis_hash
+  }
+
+  /// SS58 prefix for formatting accounts: the user's override, then the chain's.
+  pub fn ss58_prefix(&self) -> u16 {
+    self
+      .settings
+      .ss58_prefix()
+      .or(self.chain_ss58_prefix)
+      .unwrap_or(POLYMESH_SS58_PREFIX)
+  }
+
+  pub fn best_block(&self) -> BlockNumber {
+    self.best_block
+  }
 
-                events
-              },
-            )
-            .into_iter()
-            .for_each(|(_, event)| {
-              if is_best {
-                self.recent_events.push_front(event);
-              } else {
-                self.recent_events.push_back(event);
+  /// Get a cached block by number.
+  pub fn block(&self, number: BlockNumber) -> Option<&BlockInfo> {
+    self.blocks.get(&number)
+  }
+
+  /// Get a cached block by hash.
+  pub fn block_by_hash(&self, hash: &BlockHash) -> Option<&BlockInfo> {
+    self
+      .hash_to_number
+      .get(hash)
+      .and_then(|number| self.blocks.get(number))
+      // The block at that number might be from another fork now.
+      .filter(|block| block.hash == *hash)
+  }
+
+  /// Cached block numbers, newest first.
+  pub fn recent_blocks(&self) -> impl Iterator<Item = BlockNumber> + '_ {
+    self.recent_blocks.iter().copied()
+  }
+
+  /// Recent event summaries, newest first.
+  pub fn recent_events(&self) -> impl Iterator<Item = &BlockEventSummary> {
+    self.recent_events.iter()
+  }
+
+  /// Check if a block, or one of its events, is pinned.
+  pub fn is_block_pinned(&self, hash: &BlockHash) -> bool {
+    self.pinned_blocks.contains(hash)
+      || self
+        .pinned_events
+        .range((*hash, 0)..=(*hash, u32::MAX))
+        .next()
+        .is_some()
+  }
+
+  pub fn toggle_pin_block(&mut self, hash: BlockHash) {
+    if !self.pinned_blocks.remove(&hash) {
+      self.pinned_blocks.insert(hash);
+    }
+    self.need_save = true;
+  }
+
+  pub fn toggle_pin_event(&mut self, hash: BlockHash, index: u32) {
+    if !self.pinned_events.remove(&(hash, index)) {
+      self.pinned_events.insert((hash, index));
+    }
+    self.need_save = true;
+  }
+
+  /// Raw RPC responses behind a block or account, shown when RPC debugging is on or responses
+  /// were captured.  Returns `true` if the user asked to reload them.
+  pub fn raw_rpc_ui(&self, ui: &mut egui::Ui, subject: RpcSubject) -> bool {
+    let calls = self.raw_rpc.get(&subject);
+    if !self.settings.debug_rpc && calls.is_none() {
+      return false;
+    }
+    let mut reload = false;
+    egui::CollapsingHeader::new("Raw RPC responses")
+      .id_source(("raw_rpc", subject))
+      .show(ui, |ui| {
+        let Some(calls) = calls else {
+          ui.weak("Loaded before RPC debugging was turned on.");
+          reload = ui.button("Reload").clicked();
+          return;
+        };
+        for (idx, call) in calls.iter().enumerate() {
+          egui::CollapsingHeader::new(format!("{}({})", call.method, call.params))
+            .id_source(idx)
+            .show(ui, |ui| {
+              let button = ui.button("📋 Copy");
+              if button.clicked() {
+                ui.output_mut(|o| o.copied_text = call.response.clone());
               }
+              copied_ui(ui, &button, button.clicked());
+              ScrollArea::vertical()
+                .id_source(idx)
+                .max_height(200.0)
+                .show(ui, |ui| {
+                  let mut response = call.response.as_str();
+                  ui.add(
+                    TextEdit::multiline(&mut response)
+                      .code_editor()
+                      .desired_width(f32::INFINITY),
+                  );
+                });
             });
-          // Update blocks.
-          self.hash_to_number.insert(block.hash, number);
-          if self.blocks.insert(number, block).is_none() {
-            // Update recent blocks.
-            if is_best {
-              self.recent_blocks.push_front(number);
-            } else {
-              self.recent_blocks.push_back(number);
-            }
-          }
-          // Trim old events.
-          while self.recent_events.len() > MAX_RECENT_EVENTS {
-            self.recent_events.pop_back();
-          }
-          // Trim old blocks.
-          while self.recent_blocks.len() > MAX_RECENT_BLOCKS {
-            if let Some(number) = self.recent_blocks.pop_back() {
-              self.blocks.remove(&number);
+        }
+      });
+    reload
+  }
+
+  /// Number of blocks the processed best block lags the chain's announced best header.
+  pub fn blocks_behind(&self) -> BlockNumber {
+    self.best_header.saturating_sub(self.best_block)
+  }
+
+  pub fn set_url(&mut self, url: &str) {
+    self.url = url.into();
+    self.need_save = true;
+  }
+
+  /// Check the genesis hash of a node of a well-known network, to catch misconfigured
+  /// endpoints.
+  fn check_genesis(&self, genesis: BlockHash) -> Option<String> {
+    let network = NETWORKS
+      .iter()
+      .find(|network| network.url == self.connected_url)?;
+    let expected = parse_block_hash(network.genesis?)?;
+    (genesis != expected).then(|| {
+      format!(
+        "{} should have genesis {expected:?}, but the node has {genesis:?}",
+        network.name
+      )
+    })
+  }
+
+  /// Pick a well-known network and connect to it.
+  fn network_ui(&mut self, ui: &mut egui::Ui) {
+    let current = NETWORKS.iter().find(|network| network.url == self.url);
+    let mut picked = None;
+    ui.horizontal(|ui| {
+      ui.label("Network: ");
+      egui::ComboBox::from_id_source("Network")
+        .selected_text(current.map_or("Custom", |network| network.name))
+        .show_ui(ui, |ui| {
+          for network in NETWORKS {
+            let selected = current.is_some_and(|current| current.url == network.url);
+            if ui
+              .selectable_label(selected, network.name)
+              .on_hover_text(network.url)
+              .clicked()
+            {
+              picked = Some(network.url);
             }
           }
-        }
-        None => {
-          // Channel is empty.
-          break;
+        });
+    });
+    if let Some(err) = &self.genesis_mismatch {
+      ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {err}"));
+    }
+    if let Some(url) = picked {
+      if url != self.url {
+        self.set_url(url);
+        self.reconnect();
+      }
+    }
+  }
+
+  /// (Re)start the metrics server when the metrics port setting changed.
+  #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+  fn start_metrics(&mut self) {
+    let port = self.settings.metrics_port;
+    if self.metrics_port == port {
+      return;
+    }
+    self.metrics_port = port;
+    // Stop the old server first, so it releases its port.
+    self.metrics = None;
+    let Some(port) = port else {
+      return;
+    };
+    match MetricsServer::start(port) {
+      Ok(server) => {
+        self.metrics = Some(server);
+      }
+      Err(err) => {
+        log::error!("Failed to start metrics server: {err:?}");
+        self.push_error(format!(
+          "Failed to start metrics server on port {port}: {err}"
+        ));
+      }
+    }
+  }
+
+  /// Update the metrics served by the metrics server, from the session stats and chain status.
+  #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+  fn update_metrics(&mut self, now: f64) {
+    self.start_metrics();
+    if self.metrics.is_none() {
+      return;
+    }
+    self.refresh_chain_status(now);
+    let Some(server) = &self.metrics else {
+      return;
+    };
+    let stats = &self.stats;
+    let connected = self.status == ConnectionStatus::Connected;
+    let mut out = String::new();
+    #[rustfmt::skip]
+    let metrics: &[(&str, &str, &str, u64)] = &[
+      ("polymesh_explorer_connected", "gauge", "1 if connected to the node.", connected as u64),
+      ("polymesh_explorer_best_block", "gauge", "Best block processed.", self.best_block.into()),
+      ("polymesh_explorer_announced_block", "gauge", "Best block announced by the node.", self.best_header.into()),
+      ("polymesh_explorer_blocks_behind", "gauge", "Announced blocks still being processed.", self.blocks_behind().into()),
+      ("polymesh_explorer_blocks_total", "counter", "Blocks seen this session.", stats.blocks),
+      ("polymesh_explorer_events_total", "counter", "Events processed this session.", stats.events),
+      ("polymesh_explorer_decode_errors_total", "counter", "Events that failed to decode.", stats.decode_errors),
+      ("polymesh_explorer_reconnects_total", "counter", "Reconnects to the node.", stats.reconnects.into()),
+      ("polymesh_explorer_reorgs_total", "counter", "Chain reorgs seen.", stats.reorgs.into()),
+      ("polymesh_explorer_max_reorg_depth", "gauge", "Deepest reorg seen, in blocks.", stats.max_reorg_depth.into()),
+      ("polymesh_explorer_uptime_seconds", "gauge", "Session uptime.", stats.uptime().max(0) as u64),
+    ];
+    for (name, kind, help, value) in metrics {
+      write_metric(&mut out, name, kind, help, value);
+    }
+    // Left out until the node reported them.
+    if let Some(status) = &self.chain_status {
+      if let Some((finalized, _)) = status.finalized {
+        let lag = self.best_block.saturating_sub(finalized);
+        #[rustfmt::skip]
+        let metrics: &[(&str, &str, &str, u64)] = &[
+          ("polymesh_explorer_finalized_block", "gauge", "Finalized block reported by the node.", finalized.into()),
+          ("polymesh_explorer_finality_lag", "gauge", "Blocks between the best and the finalized block.", lag.into()),
+        ];
+        for (name, kind, help, value) in metrics {
+          write_metric(&mut out, name, kind, help, value);
         }
       }
+      write_metric(
+        &mut out,
+        "polymesh_explorer_latency_milliseconds",
+        "gauge",
+        "Round trip time of a request to the node.",
+        status.latency_ms,
+      );
     }
+    server.update(out);
   }
 
-  pub fn set_url(&mut self, url: &str) {
-    self.url = url.into();
-    self.need_save = true;
+  /// (Re)start forwarding events when the forwarding settings changed.
+  #[cfg(not(target_arch = "wasm32"))]
+  fn start_forwarding(&mut self) {
+    let url = self.settings.forward_url.trim();
+    let target = Some((url.to_string(), self.settings.forward_format)).filter(|_| !url.is_empty());
+    if self.forward_target == target {
+      return;
+    }
+    self.forwarder = target
+      .clone()
+      .map(|(url, format)| EventForwarder::start(url, format));
+    self.forward_target = target;
   }
 
   fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-    // Always repaint to allow pulling the backend for updates.
-    ctx.request_repaint();
+    #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+    self.update_metrics(ctx.input(|i| i.time));
+    #[cfg(not(target_arch = "wasm32"))]
+    self.start_forwarding();
+    #[cfg(not(target_arch = "wasm32"))]
+    if self.settings.check_updates && self.update_check.update() {
+      self.need_save = true;
+    }
     // Pull the backend for updates.
-    self.backend_updates();
+    let processed = self.backend_updates();
+    self.trim_blocks();
+    // The views register the blocks they show again this frame.
+    self.resident_blocks.clear();
+    if processed > 0 {
+      // Show the updates and keep draining the backend.
+      ctx.request_repaint();
+    } else {
+      // Nothing changed, only wake up to poll the backend again.
+      ctx.request_repaint_after(IDLE_REPAINT_INTERVAL);
+    }
+    self.block_rate.tick(ctx.input(|i| i.time));
   }
 
-  pub fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
-    ui.label("Polymesh: ");
-    if ui.button("Polymesh Testnet").clicked() {
-      self.set_url(POLYMESH_TESTNET);
-    }
-    if ui.button("Polymesh Staging").clicked() {
-      self.set_url(POLYMESH_STAGING);
+  /// Side panel.  Returns the view to open, e.g. from a bookmark.
+  pub fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    self.confirm_reconnect_ui(ui.ctx());
+    ui.horizontal(|ui| {
+      ui.label(format!("Status: {}", self.status));
+      if self.status == ConnectionStatus::Disconnected {
+        if ui.button("Connect").clicked() {
+          self.reconnect();
+        }
+      } else if ui.button("Disconnect").clicked() {
+        self.disconnect();
+      }
+      let behind = self.blocks_behind();
+      if behind > 0 {
+        ui.colored_label(ui.visuals().warn_fg_color, format!("behind by {behind}"))
+          .on_hover_text("Announced blocks that are still being processed");
+      }
+    });
+    if let (ConnectionStatus::Connected, Some((follow, polling))) = (self.status, self.following) {
+      let how = if polling { "polling" } else { "subscription" };
+      ui.weak(format!("Following {follow} ({how})"));
     }
-    ui.label("Custom: ");
-    if ui.button("Local").clicked() {
-      self.set_url("ws://localhost:9944/");
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(release) = self
+      .update_check
+      .available()
+      .filter(|_| self.settings.check_updates)
+    {
+      ui.hyperlink_to(
+        format!("⬆ Update available: v{}", release.version),
+        &release.url,
+      );
     }
+    ui.horizontal(|ui| {
+      let mut paused = self.preload_paused;
+      if ui.checkbox(&mut paused, "Pause preload").changed() {
+        self.set_preload_paused(paused);
+      }
+      if self.preload_blocks > 0 {
+        ui.weak(format!("({} blocks left)", self.preload_blocks));
+      }
+    });
+    ui.separator();
+    self.network_ui(ui);
     ui.horizontal(|ui| {
       ui.label("Custom node: ");
       let resp = ui.text_edit_singleline(&mut self.url);
@@ -278,6 +1685,61 @@ impl BackendState {
         self.need_save = true;
       }
     });
+    ui.horizontal(|ui| {
+      ui.label("Auth token: ");
+      let resp = ui
+        .add(TextEdit::singleline(&mut self.auth_token).password(true))
+        .on_hover_text("Sent as the `token` url parameter, not saved");
+      if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+        self.need_save = true;
+      }
+    });
+    ui.horizontal(|ui| {
+      ui.label("Archive node: ");
+      let resp = ui.text_edit_singleline(&mut self.archive_url);
+      if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+        self.need_save = true;
+      }
+    });
+
+    ui.separator();
+    egui::CollapsingHeader::new(format!("⭐ Bookmarks ({})", self.bookmarks.len()))
+      .id_source("Bookmarks")
+      .show(ui, |ui| {
+        app_event = self.bookmarks_ui(ui);
+      });
+    egui::CollapsingHeader::new("Session").show(ui, |ui| {
+      if self.stats.ui(ui) {
+        self.stats = Default::default();
+      }
+    });
+    egui::CollapsingHeader::new(format!("Errors ({})", self.error_log.len()))
+      .id_source("Errors")
+      .show(ui, |ui| {
+        self.error_log_ui(ui);
+      });
+    egui::CollapsingHeader::new("Record / Replay").show(ui, |ui| {
+      self.record_replay_ui(ui);
+    });
+    egui::CollapsingHeader::new("Settings").show(ui, |ui| {
+      let follow = self.settings.follow;
+      if self.settings.ui(ui) {
+        self.need_save = true;
+        if self.settings.follow != follow && self.status == ConnectionStatus::Connected {
+          // The new heads replace the old tip.
+          self.best_block = 0;
+          self.best_header = 0;
+          self.best_hash = None;
+          self.connect();
+        }
+        if self.status == ConnectionStatus::Connected {
+          self.set_poll_interval();
+          self.set_debug_rpc();
+          self.set_lazy_events();
+          self.set_max_event_value();
+        }
+      }
+    });
 
     ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
       ui.heading("Polymesh Rust GUI");
@@ -287,11 +1749,14 @@ impl BackendState {
       );
       egui::warn_if_debug_build(ui);
     });
+    app_event
   }
 }
 
 pub enum SubAppEvent {
   BlockDetails(BlockHash),
+  /// Open the sub-app with this anchor.
+  OpenApp(String),
 }
 
 pub trait SubApp {
@@ -308,14 +1773,126 @@ pub trait SubApp {
     ctx: &egui::Context,
     anchor: &str,
   ) -> Option<SubAppEvent>;
+
+  /// Reset the sub-app back to its default state.
+  fn reset(&mut self);
+}
+
+/// Time budget for work done in a frame.
+struct UpdateBudget {
+  /// `std::time::Instant` isn't available on the web, `web_time` falls back to it natively.
+  deadline: web_time::Instant,
+}
+
+impl UpdateBudget {
+  fn start(budget: std::time::Duration) -> Self {
+    Self {
+      deadline: web_time::Instant::now() + budget,
+    }
+  }
+
+  fn exceeded(&self) -> bool {
+    web_time::Instant::now() >= self.deadline
+  }
+}
+
+/// Check if a `TableBuilder` with columns of at least `min_width` in total can be laid out.
+///
+/// HACK(egui): `TableBuilder` misbehaves when squeezed below its columns' minimum widths or
+/// given a degenerate size, e.g. while resizing.  Callers fall back to plain rows.
+fn table_fits(ui: &egui::Ui, min_width: f32) -> bool {
+  let size = ui.available_size();
+  size.x.is_finite() && size.y.is_finite() && size.x >= min_width && size.y > 0.0
+}
+
+/// Single line label truncated to the available width, showing the full text on hover.
+fn truncated_label(ui: &mut egui::Ui, text: &str, color: Color32) -> Response {
+  let mut job = text::LayoutJob::simple_singleline(
+    text.to_string(),
+    TextStyle::Body.resolve(ui.style()),
+    color,
+  );
+  job.wrap.max_rows = 1;
+  job.wrap.break_anywhere = true;
+  ui.add(Label::new(job).wrap(true)).on_hover_text(text)
+}
+
+/// Error panel shown in place of a crashed sub-app.  Returns `true` when the user asks to reset it.
+fn app_error_ui(ctx: &egui::Context, name: &str, err: &str) -> bool {
+  let mut reset = false;
+  egui::CentralPanel::default().show(ctx, |ui| {
+    ui.heading(format!("{name} crashed"));
+    ui.label(RichText::new(err).color(ui.visuals().error_fg_color));
+    reset = ui.button("Reset").clicked();
+  });
+  reset
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+  if let Some(msg) = panic.downcast_ref::<&str>() {
+    msg.to_string()
+  } else if let Some(msg) = panic.downcast_ref::<String>() {
+    msg.clone()
+  } else {
+    "Unknown panic".to_string()
+  }
 }
 
 /// Chain Info sub-app.
 #[derive(Default, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct ChainInfoApp {
+  /// Show a header row before each block's events in the events feed.
+  group_events: bool,
+  /// List the recent blocks by number, newest first, instead of in arrival order.
+  sort_by_number: bool,
+  /// Pallets shown in the events feed, all pallets are shown when empty.
+  pallet_chips: BTreeSet<String>,
+  /// Filter expression over the events feed, see `EventFilter`.
+  event_filter: String,
+  /// `event_filter` parsed, reparsed when the expression changes.
+  #[serde(skip)]
+  parsed_filter: Option<(String, Result<EventFilter, String>)>,
+  /// Show the blocks from this block forward, instead of the blocks before the chain tip.
+  range_start: Option<BlockNumber>,
+  /// Number of blocks requested from `range_start`.
+  #[serde(skip)]
+  range_len: u32,
+  /// The first blocks from `range_start` were requested.
+  #[serde(skip)]
+  range_requested: bool,
+  /// Start block typed by the user.
+  #[serde(skip)]
+  range_input: BlockNumber,
   #[serde(skip)]
   reset_scroll: bool,
+  /// Best block seen by the last frame.
+  #[serde(skip)]
+  last_best: BlockNumber,
+  /// Time the current best block arrived.
+  #[serde(skip)]
+  best_changed_at: f64,
+  /// Blocks selected for export.
+  #[serde(skip)]
+  selected_blocks: BTreeSet<BlockNumber>,
+  /// Last clicked block, the start of a shift-click range.
+  #[serde(skip)]
+  selection_anchor: Option<BlockNumber>,
+  /// Anchor of the last frame, a new `did_events_anchor` sets the filter.
+  #[serde(skip)]
+  last_anchor: String,
+}
+
+/// Anchor of the explorer with the events feed filtered to the events mentioning `did`.
+pub fn did_events_anchor(did: &str) -> String {
+  format!("explorer/did/{did}")
+}
+
+/// Row of the events feed.
+enum EventsFeedRow<'a> {
+  /// Header before the events of a block.
+  Block(BlockNumber),
+  Event(&'a BlockEventSummary),
 }
 
 impl ChainInfoApp {
@@ -329,85 +1906,542 @@ impl ChainInfoApp {
     }
   }
 
+  /// Update the selection for a click on a block row: ctrl toggles, shift selects a range.
+  fn select_block(&mut self, number: BlockNumber, modifiers: Modifiers) {
+    match self.selection_anchor {
+      Some(anchor) if modifiers.shift => {
+        let range = anchor.min(number)..=anchor.max(number);
+        if !modifiers.command {
+          self.selected_blocks.clear();
+        }
+        self.selected_blocks.extend(range);
+        return;
+      }
+      _ if modifiers.command => {
+        if !self.selected_blocks.remove(&number) {
+          self.selected_blocks.insert(number);
+        }
+      }
+      _ => {
+        self.selected_blocks.clear();
+        self.selected_blocks.insert(number);
+      }
+    }
+    self.selection_anchor = Some(number);
+  }
+
+  /// Selected blocks as a JSON array, skipping blocks that were trimmed from the cache.
+  fn export_selected(&self, backend: &BackendState) -> String {
+    let blocks: Vec<_> = self
+      .selected_blocks
+      .iter()
+      .filter_map(|number| backend.block(*number))
+      .collect();
+    serde_json::to_string_pretty(&blocks).unwrap_or_else(|err| format!("Export failed: {err}"))
+  }
+
+  /// Choose between the blocks before the chain tip and the blocks from a chosen block.
+  fn follow_ui(&mut self, backend: &BackendState, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+      ui.label("Follow from block:");
+      ui.add(egui::DragValue::new(&mut self.range_input).clamp_range(0..=backend.best_block));
+      if ui.button("Load").clicked() {
+        if self.range_start.is_some() {
+          backend.cancel_block_range();
+        }
+        self.range_start = Some(self.range_input);
+        self.range_requested = false;
+        self.reset_scroll = true;
+      }
+      if self.range_start.is_some() && ui.button("Back to tip").clicked() {
+        backend.cancel_block_range();
+        self.range_start = None;
+        self.reset_scroll = true;
+      }
+    });
+  }
+
+  /// Blocks loaded forward from `start`, with the live tip shown separately.
+  fn range_blocks_ui(
+    &mut self,
+    backend: &BackendState,
+    ui: &mut egui::Ui,
+    start: BlockNumber,
+  ) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    if !self.range_requested {
+      self.range_requested = true;
+      self.range_len = RANGE_CHUNK;
+      backend.get_block_range(start, RANGE_CHUNK);
+    }
+    ui.horizontal(|ui| {
+      ui.label(format!("Blocks from #{start}"));
+      let best = backend.best_block;
+      if let Some(block) = backend.blocks.get(&best) {
+        ui.weak("Live tip:");
+        if ui.link(format!("#{best}")).clicked() {
+          app_event = Some(SubAppEvent::BlockDetails(block.hash));
+        }
+      }
+    });
+    // Blocks that can be loaded, the rest haven't been produced yet.
+    let expected = (backend.best_block + 1)
+      .saturating_sub(start)
+      .min(self.range_len);
+    let loaded = (start..start + expected)
+      .filter(|number| backend.blocks.contains_key(number))
+      .count() as u32;
+    if loaded < expected {
+      ui.horizontal(|ui| {
+        if ui.button("Cancel").clicked() {
+          backend.cancel_block_range();
+          // Stop at the first missing block.
+          self.range_len = (start..start + expected)
+            .take_while(|number| backend.blocks.contains_key(number))
+            .count() as u32;
+        }
+        ui.add(
+          egui::ProgressBar::new(loaded as f32 / expected as f32)
+            .text(format!("Loading {loaded}/{expected}")),
+        );
+      });
+    }
+    ui.separator();
+    ui.push_id("Range", |ui| {
+      ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+        let end = start.saturating_add(self.range_len);
+        if ui
+          .add_enabled(end <= backend.best_block, Button::new("Load more"))
+          .on_disabled_hover_text("Waiting for the chain to produce more blocks")
+          .clicked()
+        {
+          backend.get_block_range(end, RANGE_CHUNK);
+          self.range_len += RANGE_CHUNK;
+        }
+        ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+          let text_style = TextStyle::Body;
+          let row_height = ui.text_style_height(&text_style);
+          let num_rows = self.range_len as usize;
+          let mut scroll = ScrollArea::vertical().auto_shrink([false; 2]);
+          if self.reset_scroll {
+            scroll = scroll.vertical_scroll_offset(0.0);
+          }
+          scroll.show_rows(ui, row_height, num_rows, |ui, row_range| {
+            for row in row_range {
+              let number = start.saturating_add(row as BlockNumber);
+              ui.horizontal(|ui| match backend.blocks.get(&number) {
+                Some(block) => {
+                  if ui.link(format!("{number}")).clicked() {
+                    app_event = Some(SubAppEvent::BlockDetails(block.hash));
+                  }
+                  ui.label(format!("{:?}", block.hash));
+                  if let Some(ts) = block.timestamp {
+                    ui.weak(format_time(ts, backend.settings.time_zone));
+                  }
+                }
+                None if number > backend.best_block => {
+                  ui.weak(format!("{number}: not produced yet"));
+                }
+                None => {
+                  ui.weak(format!("{number}: loading..."));
+                }
+              });
+            }
+          });
+        });
+      });
+    });
+    app_event
+  }
+
   fn recent_blocks_ui(
     &mut self,
     backend: &mut BackendState,
     ui: &mut egui::Ui,
   ) -> Option<SubAppEvent> {
     let mut app_event = None;
-    ui.label("Recent blocks:");
+    let mut load_older = false;
+    let mut select = None;
+    let mut pin = None;
+    let now = ui.input(|i| i.time);
+    if backend.best_block != self.last_best {
+      self.last_best = backend.best_block;
+      self.best_changed_at = now;
+    }
+    // Fade out the new best block's highlight.
+    let flash = 1.0 - ((now - self.best_changed_at) / BEST_BLOCK_FLASH).clamp(0.0, 1.0);
+    if flash > 0.0 {
+      ui.ctx().request_repaint();
+    }
+    ui.horizontal(|ui| {
+      ui.label("Recent blocks:");
+      ui.checkbox(&mut self.sort_by_number, "Sort by number")
+        .on_hover_text("Older blocks loaded during catch-up are listed in arrival order otherwise");
+      if !self.selected_blocks.is_empty() {
+        let count = self.selected_blocks.len();
+        let button = ui.button(format!("📋 Export selected ({count})"));
+        if button.clicked() {
+          let json = self.export_selected(backend);
+          ui.output_mut(|o| o.copied_text = json);
+        }
+        copied_ui(ui, &button, button.clicked());
+        if ui.button("Clear selection").clicked() {
+          self.selected_blocks.clear();
+          self.selection_anchor = None;
+        }
+      }
+    });
     ui.separator();
     ui.push_id("Blocks", |ui| {
-      let blocks = &backend.recent_blocks;
-      let text_style = TextStyle::Body;
-      let row_height = ui.text_style_height(&text_style);
-      let num_rows = blocks.len();
-      let mut scroll = ScrollArea::vertical().auto_shrink([false; 2]);
-      if self.reset_scroll {
-        scroll = scroll.vertical_scroll_offset(0.0);
-      }
-      scroll.show_rows(ui, row_height, num_rows, |ui, row_range| {
-        if !self.validate_range(num_rows, &row_range) {
-          return;
+      ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+        if backend.can_load_older_blocks() && ui.button("Load older blocks").clicked() {
+          load_older = true;
+        }
+        if backend.recent_blocks.len() >= MAX_RECENT_BLOCKS {
+          ui.weak(format!(
+            "Showing the most recent {MAX_RECENT_BLOCKS} blocks (older trimmed)"
+          ));
         }
+        ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+          let mut blocks: Vec<BlockNumber> = backend.recent_blocks().collect();
+          if self.sort_by_number {
+            blocks.sort_unstable_by(|a, b| b.cmp(a));
+          }
+          let text_style = TextStyle::Body;
+          let row_height = ui.text_style_height(&text_style);
+          let num_rows = blocks.len();
+          let mut scroll = ScrollArea::vertical().auto_shrink([false; 2]);
+          if self.reset_scroll {
+            scroll = scroll.vertical_scroll_offset(0.0);
+          }
+          scroll.show_rows(ui, row_height, num_rows, |ui, row_range| {
+            if !self.validate_range(num_rows, &row_range) {
+              return;
+            }
 
-        for number in blocks.range(row_range) {
-          let block = backend.blocks.get(number).unwrap();
-          ui.horizontal(|ui| {
-            if ui.link(format!("{}", block.number())).clicked() {
-              app_event = Some(SubAppEvent::BlockDetails(block.hash));
+            for number in &blocks[row_range] {
+              let block = backend.blocks.get(number).unwrap();
+              let background = ui.painter().add(Shape::Noop);
+              let row = ui.horizontal(|ui| {
+                if ui.link(format!("{}", block.number())).clicked() {
+                  app_event = Some(SubAppEvent::BlockDetails(block.hash));
+                }
+                let selected = self.selected_blocks.contains(number);
+                let resp = ui
+                  .selectable_label(selected, format!("{:?}", block.hash))
+                  .on_hover_text("Click to select, ctrl/shift-click to select more");
+                if resp.clicked() {
+                  select = Some((*number, ui.input(|i| i.modifiers)));
+                }
+                if let Some(ts) = block.timestamp {
+                  ui.weak(format_time(ts, backend.settings.time_zone));
+                }
+                let pinned = backend.pinned_blocks.contains(&block.hash);
+                if ui
+                  .selectable_label(pinned, "📌")
+                  .on_hover_text(if pinned { "Unpin" } else { "Pin" })
+                  .clicked()
+                {
+                  pin = Some(block.hash);
+                }
+              });
+              if flash > 0.0 && *number == self.last_best {
+                let color = ui.visuals().selection.bg_fill.linear_multiply(flash as f32);
+                ui.painter().set(
+                  background,
+                  Shape::rect_filled(row.response.rect, 2.0, color),
+                );
+              }
             }
-            ui.label(format!("{:?}", block.hash));
           });
-        }
+        });
       });
     });
+    if let Some((number, modifiers)) = select {
+      self.select_block(number, modifiers);
+    }
+    if let Some(hash) = pin {
+      backend.toggle_pin_block(hash);
+    }
+    if load_older {
+      backend.load_older_blocks();
+    }
     app_event
   }
 
+  /// Filter expression editor.  Returns the filter to apply, `None` when it is empty or doesn't
+  /// parse.
+  fn event_filter_ui(&mut self, ui: &mut egui::Ui) -> Option<EventFilter> {
+    ui.horizontal(|ui| {
+      ui.label("Filter:");
+      let resp = ui
+        .add(
+          TextEdit::singleline(&mut self.event_filter)
+            .hint_text("name startsWith \"Asset.\" and count > 1")
+            .code_editor()
+            .desired_width(f32::INFINITY),
+        )
+        .on_hover_text(FILTER_HELP);
+      if resp.changed() {
+        self.reset_scroll = true;
+      }
+    });
+    let expr = self.event_filter.trim();
+    if expr.is_empty() {
+      self.parsed_filter = None;
+      return None;
+    }
+    if self
+      .parsed_filter
+      .as_ref()
+      .map(|(parsed, _)| parsed.as_str())
+      != Some(expr)
+    {
+      self.parsed_filter = Some((expr.to_string(), EventFilter::parse(expr)));
+    }
+    match &self.parsed_filter.as_ref()?.1 {
+      Ok(filter) => Some(filter.clone()),
+      Err(err) => {
+        ui.colored_label(ui.visuals().error_fg_color, format!("Filter: {err}"));
+        None
+      }
+    }
+  }
+
+  /// Quick-filter chips for the default pallets and the most frequent ones in the feed.
+  fn pallet_chips_ui(&mut self, backend: &BackendState, ui: &mut egui::Ui) {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for event in &backend.recent_events {
+      *counts.entry(event_pallet(&event.name)).or_default() += 1;
+    }
+    let mut frequent: Vec<_> = counts.into_iter().collect();
+    frequent.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then(a_name.cmp(b_name)));
+    let mut chips: Vec<&str> = DEFAULT_PALLET_CHIPS.to_vec();
+    for (pallet, _) in frequent.into_iter().take(FREQUENT_PALLET_CHIPS) {
+      if !chips.contains(&pallet) {
+        chips.push(pallet);
+      }
+    }
+    // Keep selected chips visible, so they can be turned off.
+    let selected: Vec<String> = self.pallet_chips.iter().cloned().collect();
+    for pallet in &selected {
+      if !chips.contains(&pallet.as_str()) {
+        chips.push(pallet);
+      }
+    }
+    ui.horizontal_wrapped(|ui| {
+      for pallet in chips {
+        let enabled = self.pallet_chips.contains(pallet);
+        let color = pallet_color(pallet, ui.visuals().dark_mode);
+        if ui
+          .selectable_label(enabled, RichText::new(pallet).color(color))
+          .clicked()
+        {
+          if enabled {
+            self.pallet_chips.remove(pallet);
+          } else {
+            self.pallet_chips.insert(pallet.to_string());
+          }
+          self.reset_scroll = true;
+        }
+      }
+      if !self.pallet_chips.is_empty() && ui.small_button("Clear").clicked() {
+        self.pallet_chips.clear();
+        self.reset_scroll = true;
+      }
+    });
+  }
+
   fn recent_events_ui(
     &mut self,
     backend: &mut BackendState,
     ui: &mut egui::Ui,
   ) -> Option<SubAppEvent> {
     let mut app_event = None;
-    ui.label("Recent events:");
+    let mut pin = None;
+    ui.horizontal(|ui| {
+      ui.label("Recent events:");
+      ui.checkbox(&mut self.group_events, "Group by block");
+      if backend.summarized_blocks > 0 {
+        ui.colored_label(
+          ui.visuals().warn_fg_color,
+          format!("⚠ {} blocks summarized", backend.summarized_blocks),
+        )
+        .on_hover_text(
+          "Blocks arrived faster than they could be shown.  Their events are counted in the \
+           stats, but left out of this feed.",
+        );
+      }
+    });
+    self.pallet_chips_ui(backend, ui);
+    let filter = self.event_filter_ui(ui);
     ui.separator();
     ui.push_id("Events", |ui| {
-      let events = &backend.recent_events;
-      let text_style = TextStyle::Body;
-      let row_height = ui.text_style_height(&text_style);
-      let num_rows = events.len();
-      let mut scroll = ScrollArea::vertical().auto_shrink([false; 2]);
-      if self.reset_scroll {
-        scroll = scroll.vertical_scroll_offset(0.0);
-      }
-      self.reset_scroll = false;
-      scroll.show_rows(ui, row_height, num_rows, |ui, row_range| {
-        if !self.validate_range(num_rows, &row_range) {
-          return;
+      ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+        if backend.recent_events.len() >= MAX_RECENT_EVENTS {
+          ui.weak(format!(
+            "Showing the most recent {MAX_RECENT_EVENTS} events (older trimmed)"
+          ));
         }
+        ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+          let mut last_block = None;
+          let rows: Vec<_> = backend
+            .recent_events
+            .iter()
+            .filter(|event| {
+              self.pallet_chips.is_empty() || self.pallet_chips.contains(event_pallet(&event.name))
+            })
+            .filter(|event| {
+              let Some(filter) = &filter else {
+                return true;
+              };
+              let value = backend
+                .blocks
+                .get(&event.block)
+                .and_then(|block| block.events.get(event.number as usize))
+                .map(|event| &event.value);
+              filter.matches(&FilterEvent {
+                name: &event.name,
+                block: event.block,
+                index: event.number,
+                count: event.count,
+                value,
+              })
+            })
+            .flat_map(|event| {
+              // Events are ordered by block, so a new block starts a new group.
+              let header = (self.group_events && last_block != Some(event.block))
+                .then_some(EventsFeedRow::Block(event.block));
+              last_block = Some(event.block);
+              header.into_iter().chain([EventsFeedRow::Event(event)])
+            })
+            .collect();
+          let text_style = TextStyle::Body;
+          let row_height = ui.text_style_height(&text_style);
+          let num_rows = rows.len();
+          let mut scroll = ScrollArea::vertical().auto_shrink([false; 2]);
+          if self.reset_scroll {
+            scroll = scroll.vertical_scroll_offset(0.0);
+          }
+          self.reset_scroll = false;
+          scroll.show_rows(ui, row_height, num_rows, |ui, row_range| {
+            if !self.validate_range(num_rows, &row_range) {
+              return;
+            }
+
+            for row in &rows[row_range] {
+              let event = match row {
+                EventsFeedRow::Block(number) => {
+                  ui.vertical_centered(|ui| {
+                    ui.weak(format!("— Block {number} —"));
+                  });
+                  continue;
+                }
+                EventsFeedRow::Event(event) => event,
+              };
+              ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                  let hash = backend.blocks.get(&event.block).map(|block| block.hash);
+                  if let Some(hash) = hash {
+                    let pinned = backend.pinned_events.contains(&(hash, event.number));
+                    if ui
+                      .selectable_label(pinned, "📌")
+                      .on_hover_text(if pinned { "Unpin" } else { "Pin" })
+                      .clicked()
+                    {
+                      pin = Some((hash, event.number));
+                    }
+                  }
+                  if ui
+                    .link(format!("{}-{}", event.block, event.number))
+                    .clicked()
+                  {
+                    if let Some(hash) = hash {
+                      app_event = Some(SubAppEvent::BlockDetails(hash));
+                    }
+                  }
+                  if event.count > 1 {
+                    ui.label(format!("({}x)", event.count));
+                  }
+                  if let Some(ts) = event.timestamp {
+                    ui.weak(format_time(ts, backend.settings.time_zone));
+                  }
+                  // The event name gets the space left over by the link and count.
+                  ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                    let color = pallet_color(&event.name, ui.visuals().dark_mode);
+                    truncated_label(ui, &event.name, color);
+                  });
+                });
+              });
+            }
+          });
+        });
+      });
+    });
+    if let Some((hash, index)) = pin {
+      backend.toggle_pin_event(hash, index);
+    }
+    app_event
+  }
 
-        for event in events.range(row_range) {
+  /// Pinned blocks and events.  Blocks that aren't cached (e.g. after a restart) load when opened.
+  fn pinned_ui(&mut self, backend: &mut BackendState, ui: &mut egui::Ui) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    let mut unpin_block = None;
+    let mut unpin_event = None;
+    let count = backend.pinned_blocks.len() + backend.pinned_events.len();
+    egui::CollapsingHeader::new(format!("📌 Pinned ({count})"))
+      .id_source("Pinned")
+      .show(ui, |ui| {
+        if count == 0 {
+          ui.weak("Pin blocks and events with 📌 to keep them here.");
+        }
+        for hash in &backend.pinned_blocks {
           ui.horizontal(|ui| {
-            ui.label(event.name.to_string());
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-              if ui
-                .link(format!("{}-{}", event.block, event.number))
-                .clicked()
-              {
-                if let Some(block) = backend.blocks.get(&event.block) {
-                  app_event = Some(SubAppEvent::BlockDetails(block.hash));
+            if ui.small_button("✖").on_hover_text("Unpin").clicked() {
+              unpin_block = Some(*hash);
+            }
+            match backend.block_by_hash(hash) {
+              Some(block) => {
+                if ui.link(format!("#{}", block.number())).clicked() {
+                  app_event = Some(SubAppEvent::BlockDetails(*hash));
                 }
+                ui.label(format!("{hash:?}"));
               }
-              if event.count > 1 {
-                ui.label(format!("({}x)", event.count));
+              None => {
+                if ui.link(format!("{hash:?}")).clicked() {
+                  app_event = Some(SubAppEvent::BlockDetails(*hash));
+                }
               }
-            });
+            }
+          });
+        }
+        for (hash, index) in &backend.pinned_events {
+          ui.horizontal(|ui| {
+            if ui.small_button("✖").on_hover_text("Unpin").clicked() {
+              unpin_event = Some((*hash, *index));
+            }
+            let block = backend.block_by_hash(hash);
+            let label = match block {
+              Some(block) => format!("{}-{index}", block.number()),
+              None => format!("{hash:?}-{index}"),
+            };
+            if ui.link(label).clicked() {
+              app_event = Some(SubAppEvent::BlockDetails(*hash));
+            }
+            if let Some(event) = block.and_then(|block| block.events.get(*index as usize)) {
+              let color = pallet_color(&event.name, ui.visuals().dark_mode);
+              ui.colored_label(color, event.name.as_ref());
+            }
           });
         }
       });
-    });
+    if let Some(hash) = unpin_block {
+      backend.toggle_pin_block(hash);
+    }
+    if let Some((hash, index)) = unpin_event {
+      backend.toggle_pin_event(hash, index);
+    }
     app_event
   }
 }
@@ -429,10 +2463,24 @@ impl SubApp for ChainInfoApp {
     &mut self,
     backend: &mut BackendState,
     ctx: &egui::Context,
-    _anchor: &str,
+    anchor: &str,
   ) -> Option<SubAppEvent> {
+    if self.last_anchor != anchor {
+      self.last_anchor = anchor.to_string();
+      // Only well formed DIDs, the anchor ends up in the filter expression.
+      let did = anchor.strip_prefix("explorer/did/").filter(|did| {
+        did
+          .strip_prefix("0x")
+          .is_some_and(|hex| hex::decode(hex).is_ok())
+      });
+      if let Some(did) = did {
+        self.event_filter = format!("value contains \"{did}\"");
+        self.reset_scroll = true;
+      }
+    }
     let mut app_event = None;
     egui::CentralPanel::default().show(ctx, |ui| {
+      app_event = self.pinned_ui(backend, ui);
       let height = ui.available_height();
       ui.horizontal(|ui| {
         ui.set_height(height);
@@ -442,7 +2490,16 @@ impl SubApp for ChainInfoApp {
           ui.set_height(height);
           ui.set_width(width);
           ui.vertical(|ui| {
-            if let Some(event) = self.recent_blocks_ui(backend, ui) {
+            self.follow_ui(backend, ui);
+            let event = match self.range_start {
+              Some(start) => {
+                // Keep the loaded range cached, when older blocks are trimmed.
+                backend.keep_blocks(start..start.saturating_add(self.range_len));
+                self.range_blocks_ui(backend, ui, start)
+              }
+              None => self.recent_blocks_ui(backend, ui),
+            };
+            if let Some(event) = event {
               app_event = Some(event);
             }
           });
@@ -462,6 +2519,97 @@ impl SubApp for ChainInfoApp {
     });
     app_event
   }
+
+  fn reset(&mut self) {
+    *self = Default::default();
+  }
+}
+
+/// Pallet of an event name, e.g. `Asset` for `Asset.AssetCreated`.
+fn event_pallet(name: &str) -> &str {
+  name.split_once('.').map_or(name, |(pallet, _)| pallet)
+}
+
+/// Parse a `0x` prefixed block hash.
+fn parse_block_hash(param: &str) -> Option<BlockHash> {
+  let raw = hex::decode(param.strip_prefix("0x")?).ok()?;
+  if raw.len() == BlockHash::len_bytes() {
+    Some(BlockHash::from_slice(raw.as_slice()))
+  } else {
+    None
+  }
+}
+
+/// What a pasted identifier could refer to.
+#[derive(Clone, Copy, Debug)]
+enum JumpTarget {
+  Number(BlockNumber),
+  Hash(BlockHash),
+  Extrinsic(TxHash),
+  Account(AccountKey),
+}
+
+impl JumpTarget {
+  /// All the ways `input` can be read.  A `0x` hash is ambiguous, it could be a block hash, an
+  /// extrinsic hash or an account key.
+  fn classify(input: &str) -> Vec<Self> {
+    let input = input.trim();
+    if input.starts_with("0x") {
+      match parse_block_hash(input) {
+        Some(hash) => vec![
+          Self::Hash(hash),
+          Self::Extrinsic(hash),
+          Self::Account(hash.to_fixed_bytes()),
+        ],
+        None => vec![],
+      }
+    } else if let Ok(number) = input.parse() {
+      vec![Self::Number(number)]
+    } else {
+      parse_account(input)
+        .map(Self::Account)
+        .into_iter()
+        .collect()
+    }
+  }
+
+  fn label(&self) -> &'static str {
+    match self {
+      Self::Number(_) | Self::Hash(_) => "View as block",
+      Self::Extrinsic(_) => "View as extrinsic",
+      Self::Account(_) => "View as account",
+    }
+  }
+}
+
+/// Block header and events as tab separated values, for pasting into a spreadsheet.
+fn block_tsv(block: &BlockInfo, settings: &Settings) -> String {
+  // Tabs and newlines would break the table.
+  let cell = |value: String| value.as_str().replace(['\t', '\n', '\r'], " ");
+  let time = block
+    .timestamp
+    .map(|ts| format_time(ts, settings.time_zone))
+    .unwrap_or_default();
+  let mut tsv = String::from("Number\tTime\tHash\tParent\tExtrinsics\tState\n");
+  tsv.push_str(&format!(
+    "{}\t{}\t{:?}\t{:?}\t{:?}\t{:?}\n\n",
+    block.number(),
+    time,
+    block.hash,
+    block.header.parent_hash,
+    block.header.extrinsics_root,
+    block.header.state_root
+  ));
+  tsv.push_str("Phase\tName\tValue\n");
+  for event in &block.events {
+    tsv.push_str(&format!(
+      "{}\t{}\t{}\n",
+      cell(format!("{:?}", event.phase)),
+      event.name,
+      cell(event.value.to_string())
+    ));
+  }
+  tsv
 }
 
 /// Selected block
@@ -471,6 +2619,139 @@ pub enum SelectedBlock {
   Number(BlockNumber),
   #[default]
   Best,
+  /// The zero hash, e.g. from a default initialized anchor.  Nodes don't have a block for it.
+  None,
+}
+
+/// Consistency checks of a block header against the cached previous block.  `None` when the
+/// previous block isn't cached.
+struct HeaderChecks {
+  /// The parent hash is the previous block's hash.
+  parent_linked: Option<bool>,
+  extrinsics_changed: Option<bool>,
+  state_changed: Option<bool>,
+  /// Extrinsics seen in the event phases and decoded extrinsics, when they disagree the events
+  /// or extrinsics weren't decoded correctly.
+  extrinsic_counts: Option<(usize, usize)>,
+  /// Spec version of the previous block's runtime, when the block ran under another runtime.
+  upgraded_from: Option<u32>,
+}
+
+impl HeaderChecks {
+  fn new(block: &BlockInfo, parent: Option<&BlockInfo>) -> Self {
+    Self {
+      parent_linked: parent.map(|parent| block.header.parent_hash == parent.hash),
+      extrinsics_changed: parent
+        .map(|parent| block.header.extrinsics_root != parent.header.extrinsics_root),
+      state_changed: parent.map(|parent| block.header.state_root != parent.header.state_root),
+      extrinsic_counts: Self::extrinsic_counts(block),
+      upgraded_from: parent
+        .and_then(|parent| parent.spec_version)
+        .filter(|old| block.spec_version.is_some_and(|spec| spec != *old)),
+    }
+  }
+
+  /// The block's runtime version, marking runtime upgrades.
+  fn spec_version_ui(&self, ui: &mut egui::Ui, block: &BlockInfo) {
+    let Some(spec) = block.spec_version else {
+      ui.weak("unknown");
+      return;
+    };
+    if let Some(old) = self.upgraded_from {
+      ui.colored_label(ui.visuals().warn_fg_color, "⬆")
+        .on_hover_text(format!(
+          "Runtime upgraded from {old}, neighbouring blocks can decode differently"
+        ));
+    }
+    ui.label(format!("v{spec}"));
+  }
+
+  /// Every extrinsic emits at least one event (`ExtrinsicSuccess` or `ExtrinsicFailed`).  Not
+  /// checked when the events or extrinsics weren't loaded.
+  fn extrinsic_counts(block: &BlockInfo) -> Option<(usize, usize)> {
+    if block.events.is_empty() || block.extrinsics.is_empty() {
+      return None;
+    }
+    let applied: BTreeSet<u32> = block
+      .events
+      .iter()
+      .filter_map(|event| match event.phase {
+        Phase::ApplyExtrinsic(idx) => Some(idx),
+        _ => None,
+      })
+      .collect();
+    Some((applied.len(), block.extrinsics.len()))
+  }
+
+  fn parent_ui(&self, ui: &mut egui::Ui) {
+    check_ui(
+      ui,
+      self.parent_linked,
+      "Chains to the cached previous block",
+      "Doesn't chain to the cached previous block, it is on another fork",
+    );
+  }
+
+  fn extrinsics_ui(&self, ui: &mut egui::Ui) {
+    check_ui(
+      ui,
+      self.extrinsics_changed,
+      "Changed from the previous block",
+      "Same extrinsics root as the previous block",
+    );
+    if let Some((applied, decoded)) = self.extrinsic_counts {
+      check_ui(
+        ui,
+        Some(applied == decoded),
+        &format!("Events of all {decoded} extrinsics"),
+        &format!("Events of {applied} extrinsics, but {decoded} extrinsics were decoded"),
+      );
+    }
+  }
+
+  fn state_ui(&self, ui: &mut egui::Ui) {
+    check_ui(
+      ui,
+      self.state_changed,
+      "Changed from the previous block",
+      "Same state root as the previous block",
+    );
+  }
+}
+
+/// Briefly confirm a copy to the clipboard next to the copy button.  `copied` is `true` in the
+/// frame the button copied something.
+fn copied_ui(ui: &egui::Ui, button: &Response, copied: bool) {
+  let id = button.id.with("copied");
+  let now = ui.input(|i| i.time);
+  if copied {
+    ui.data_mut(|d| d.insert_temp(id, now));
+  }
+  let Some(copied_at) = ui.data(|d| d.get_temp::<f64>(id)) else {
+    return;
+  };
+  if now - copied_at > COPIED_FEEDBACK {
+    ui.data_mut(|d| d.remove::<f64>(id));
+    return;
+  }
+  egui::show_tooltip_for(ui.ctx(), id, &button.rect, |ui| {
+    ui.colored_label(Color32::GREEN, "✔ Copied!");
+  });
+  ui.ctx().request_repaint();
+}
+
+/// Mark a check as passed or failed, nothing if it couldn't be checked.
+fn check_ui(ui: &mut egui::Ui, check: Option<bool>, ok: &str, failed: &str) {
+  match check {
+    Some(true) => {
+      ui.colored_label(Color32::GREEN, "✔").on_hover_text(ok);
+    }
+    Some(false) => {
+      ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+        .on_hover_text(failed);
+    }
+    None => (),
+  }
 }
 
 /// Chain Info sub-app.
@@ -480,30 +2761,194 @@ pub struct BlockDetailsApp {
   last_anchor: String,
   selected_block: SelectedBlock,
   requested: bool,
+  /// The block selected by the anchor.
+  view: BlockView,
+  /// Blocks opened in their own windows, for comparing blocks side by side.
+  windows: Vec<BlockWindow>,
+  /// Recently visited blocks, oldest first.  The last one is the shown block.
+  #[serde(skip)]
+  breadcrumb: Vec<(BlockNumber, BlockHash)>,
+  /// The last breadcrumb block was shown as the best block, it is replaced when the best block
+  /// moves on.
+  #[serde(skip)]
+  breadcrumb_at_best: bool,
+}
+
+/// View of one block, in the main panel or a block window.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct BlockView {
+  /// Block number we are waiting on the chain to produce.
+  #[serde(skip)]
+  pending_number: Option<BlockNumber>,
+  /// Index of the event whose value is shown in a window.
+  #[serde(skip)]
+  open_event: Option<u32>,
+  /// Extrinsic whose events are highlighted.
+  highlight_extrinsic: Option<u32>,
+  /// Group the block's calls by signer, instead of by extrinsic index.
+  group_by_signer: bool,
+  /// Show all of the block's events, not just the first `MAX_SHOWN_EVENTS`.
+  #[serde(skip)]
+  show_all_events: bool,
+  /// Elided event whose full value was requested.
+  #[serde(skip)]
+  value_requested: Option<(BlockHash, u32)>,
+  /// Header only block whose events were requested.
+  #[serde(skip)]
+  events_requested: Option<BlockHash>,
+  /// Block whose events decoding trace was requested.
+  #[serde(skip)]
+  trace_requested: Option<BlockHash>,
+}
+
+/// Block opened in its own window.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct BlockWindow {
+  hash: BlockHash,
+  view: BlockView,
+  #[serde(skip)]
+  requested: bool,
+}
+
+impl BlockWindow {
+  fn new(hash: BlockHash) -> Self {
+    Self {
+      hash,
+      view: Default::default(),
+      requested: false,
+    }
+  }
+
+  /// Show the window.  Returns `false` when the user closed it.
+  ///
+  /// Links to other blocks open in the window, so related blocks can be traced side by side.
+  fn show(&mut self, ctx: &egui::Context, backend: &BackendState) -> (bool, Option<SubAppEvent>) {
+    let mut open = true;
+    let mut app_event = None;
+    let block = backend.block_by_hash(&self.hash);
+    let title = match block {
+      Some(block) => format!("Block #{}", block.number()),
+      None => format!("Block {:?}", self.hash),
+    };
+    egui::Window::new(title)
+      .id(Id::new(("block_window", self.hash)))
+      .open(&mut open)
+      .default_size([700.0, 500.0])
+      .show(ctx, |ui| {
+        if let Some(number) = self.view.pending_number {
+          app_event = self.view.pending_block_ui(ui, backend, number);
+          return;
+        }
+        let Some(block) = block else {
+          if !self.requested {
+            self.requested = true;
+            backend.get_block_info(self.hash);
+          }
+          ui.label("Loading block...");
+          return;
+        };
+        app_event = self.view.block_nav_ui(ui, backend, block);
+        if let Some(event) = self.view.show_block_ui(ui, backend, block) {
+          app_event = Some(event);
+        }
+      });
+    match app_event {
+      Some(SubAppEvent::BlockDetails(hash)) => {
+        self.hash = hash;
+        self.view = BlockView {
+          group_by_signer: self.view.group_by_signer,
+          ..Default::default()
+        };
+        self.requested = false;
+        (open, None)
+      }
+      app_event => (open, app_event),
+    }
+  }
 }
 
 impl BlockDetailsApp {
+  /// Add the shown block to the breadcrumb.  Going back to a block on the breadcrumb drops the
+  /// blocks visited after it.
+  fn visit(&mut self, block: &BlockInfo) {
+    if self.breadcrumb.last().map(|(_, hash)| hash) == Some(&block.hash) {
+      return;
+    }
+    let at_best = matches!(self.selected_block, SelectedBlock::Best);
+    if let Some(pos) = self
+      .breadcrumb
+      .iter()
+      .position(|(_, hash)| *hash == block.hash)
+    {
+      self.breadcrumb.truncate(pos + 1);
+    } else {
+      if at_best && self.breadcrumb_at_best {
+        self.breadcrumb.pop();
+      }
+      self.breadcrumb.push((block.number(), block.hash));
+      if self.breadcrumb.len() > MAX_BREADCRUMB {
+        self.breadcrumb.remove(0);
+      }
+    }
+    self.breadcrumb_at_best = at_best;
+  }
+
+  /// Links back to the recently visited blocks.
+  fn breadcrumb_ui(&self, ui: &mut egui::Ui) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    let (current, visited) = self.breadcrumb.split_last()?;
+    if visited.is_empty() {
+      return None;
+    }
+    ui.horizontal_wrapped(|ui| {
+      ui.weak("Visited:");
+      for (number, hash) in visited {
+        if ui
+          .link(format!("#{number}"))
+          .on_hover_text(format!("{hash:?}"))
+          .clicked()
+        {
+          app_event = Some(SubAppEvent::BlockDetails(*hash));
+        }
+        ui.weak("›");
+      }
+      ui.strong(format!("#{}", current.0));
+    });
+    app_event
+  }
+
   fn parse_anchor_and_load_block<'a>(
     &mut self,
-    backend: &'a mut BackendState,
+    backend: &'a BackendState,
     anchor: &str,
   ) -> Result<Option<&'a BlockInfo>, String> {
     // If the nav `anchor` changed, then update our block hash to display.
     if self.last_anchor != anchor {
       self.last_anchor = anchor.to_string();
+      self.view.pending_number = None;
+      self.view.open_event = None;
+      self.view.highlight_extrinsic = None;
+      self.view.show_all_events = false;
       if let Some(param) = anchor.strip_prefix(self.anchor()) {
+        // `<block>/<extrinsic index>` highlights an extrinsic.
+        let param = match param.split_once('/') {
+          Some((param, index)) => {
+            let index = index
+              .parse()
+              .map_err(|_| format!("Invalid extrinsic index: {index:?}"))?;
+            self.view.highlight_extrinsic = Some(index);
+            param
+          }
+          None => param,
+        };
         if param.is_empty() {
           self.selected_block = SelectedBlock::Best;
         } else if param.starts_with("0x") {
-          // Parse block hash.
-          let hash = hex::decode(&param.as_bytes()[2..]).ok().and_then(|raw| {
-            if raw.len() == BlockHash::len_bytes() {
-              Some(BlockHash::from_slice(raw.as_slice()))
-            } else {
-              None
+          match parse_block_hash(param) {
+            Some(hash) if hash == BlockHash::default() => {
+              self.selected_block = SelectedBlock::None;
             }
-          });
-          match hash {
             Some(hash) => {
               self.selected_block = SelectedBlock::Hash(hash);
               self.requested = false;
@@ -512,6 +2957,9 @@ impl BlockDetailsApp {
               return Err(format!("Failed to parse block hash: {param:?}"));
             }
           }
+        } else if let Ok(number) = param.parse::<BlockNumber>() {
+          self.selected_block = SelectedBlock::Number(number);
+          self.requested = false;
         } else {
           return Err(format!("Unsupported block number lookup: {}", param));
         }
@@ -523,10 +2971,7 @@ impl BlockDetailsApp {
     Ok(match &self.selected_block {
       SelectedBlock::Hash(hash) => {
         // Check if the block is already loaded.
-        let block = backend
-          .hash_to_number
-          .get(hash)
-          .and_then(|number| backend.blocks.get(number));
+        let block = backend.block_by_hash(hash);
         if block.is_some() {
           // The block is loaded, return it.
           block
@@ -539,23 +2984,78 @@ impl BlockDetailsApp {
           None
         }
       }
-      SelectedBlock::Number(number) => backend.blocks.get(number),
+      SelectedBlock::Number(number) => {
+        let block = backend.blocks.get(number);
+        if block.is_none() {
+          if !self.requested {
+            self.requested = true;
+            backend.get_block_by_number(*number);
+          } else if backend.missing_blocks.contains(number) {
+            if *number > backend.best_block {
+              // Show the block once the chain produces it.
+              self.view.pending_number = Some(*number);
+            } else {
+              return Err(format!("Block #{number} not found"));
+            }
+          }
+        }
+        block
+      }
       SelectedBlock::Best => {
         let number = backend.best_block;
         backend.blocks.get(&number)
       }
+      SelectedBlock::None => None,
     })
   }
 
-  fn block_header_ui(&self, ui: &mut egui::Ui, block: &BlockInfo) -> Option<SubAppEvent> {
+  /// Open a block in its own window, or bring its window to the front.
+  fn open_window(&mut self, ctx: &egui::Context, hash: BlockHash) {
+    if !self.windows.iter().any(|window| window.hash == hash) {
+      self.windows.push(BlockWindow::new(hash));
+    }
+    ctx.move_to_top(egui::LayerId::new(
+      egui::Order::Middle,
+      Id::new(("block_window", hash)),
+    ));
+  }
+
+  fn windows_ui(&mut self, ctx: &egui::Context, backend: &BackendState) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    self.windows.retain_mut(|window| {
+      let (open, event) = window.show(ctx, backend);
+      if event.is_some() {
+        app_event = event;
+      }
+      open
+    });
+    app_event
+  }
+}
+
+impl BlockView {
+  /// `parent` is the cached block before `block`, for checking that the headers chain.
+  fn block_header_ui(
+    &self,
+    ui: &mut egui::Ui,
+    settings: &Settings,
+    block: &BlockInfo,
+    parent: Option<&BlockInfo>,
+  ) -> Option<SubAppEvent> {
     let mut app_event = None;
     let width = ui.available_width();
     ui.set_width(width);
+    let checks = HeaderChecks::new(block, parent);
+    if !table_fits(ui, BLOCK_HEADER_MIN_WIDTH) {
+      return self.block_header_fallback_ui(ui, settings, block, &checks);
+    }
     let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
     TableBuilder::new(ui)
       .striped(true)
       .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
       .column(Column::initial(100.0).at_least(60.0))
+      .column(Column::initial(160.0).at_least(60.0))
+      .column(Column::initial(80.0).at_least(60.0))
       .column(Column::remainder().at_least(60.0))
       .column(Column::remainder().at_least(60.0))
       .column(Column::remainder().at_least(60.0))
@@ -565,6 +3065,12 @@ impl BlockDetailsApp {
         header.col(|ui| {
           ui.heading("Number");
         });
+        header.col(|ui| {
+          ui.heading("Time");
+        });
+        header.col(|ui| {
+          ui.heading("Runtime");
+        });
         header.col(|ui| {
           ui.heading("Hash");
         });
@@ -583,18 +3089,29 @@ impl BlockDetailsApp {
           row.col(|ui| {
             ui.label(format!("{}", block.number()));
           });
+          row.col(|ui| {
+            if let Some(ts) = block.timestamp {
+              ui.label(format_time(ts, settings.time_zone));
+            }
+          });
+          row.col(|ui| {
+            checks.spec_version_ui(ui, block);
+          });
           row.col(|ui| {
             ui.label(format!("{}", block.hash));
           });
           row.col(|ui| {
+            checks.parent_ui(ui);
             if ui.link(format!("{}", block.header.parent_hash)).clicked() {
               app_event = Some(SubAppEvent::BlockDetails(block.header.parent_hash));
             }
           });
           row.col(|ui| {
+            checks.extrinsics_ui(ui);
             ui.label(format!("{:?}", block.header.extrinsics_root));
           });
           row.col(|ui| {
+            checks.state_ui(ui);
             ui.label(format!("{:?}", block.header.state_root));
           });
         })
@@ -602,9 +3119,159 @@ impl BlockDetailsApp {
     app_event
   }
 
-  fn block_extrinsics_ui(&self, ui: &mut egui::Ui, block: &BlockInfo) {
+  /// Block header as a plain grid, when the header table doesn't fit.
+  fn block_header_fallback_ui(
+    &self,
+    ui: &mut egui::Ui,
+    settings: &Settings,
+    block: &BlockInfo,
+    checks: &HeaderChecks,
+  ) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    ScrollArea::both().show(ui, |ui| {
+      egui::Grid::new("block_header_fallback")
+        .num_columns(2)
+        .show(ui, |ui| {
+          ui.label("Number:");
+          ui.label(block.number().to_string());
+          ui.end_row();
+          ui.label("Time:");
+          if let Some(ts) = block.timestamp {
+            ui.label(format_time(ts, settings.time_zone));
+          }
+          ui.end_row();
+          ui.label("Runtime:");
+          ui.horizontal(|ui| {
+            checks.spec_version_ui(ui, block);
+          });
+          ui.end_row();
+          ui.label("Hash:");
+          ui.label(format!("{}", block.hash));
+          ui.end_row();
+          ui.label("Parent:");
+          ui.horizontal(|ui| {
+            checks.parent_ui(ui);
+            if ui.link(format!("{}", block.header.parent_hash)).clicked() {
+              app_event = Some(SubAppEvent::BlockDetails(block.header.parent_hash));
+            }
+          });
+          ui.end_row();
+          ui.label("Extrinsics:");
+          ui.horizontal(|ui| {
+            checks.extrinsics_ui(ui);
+            ui.label(format!("{:?}", block.header.extrinsics_root));
+          });
+          ui.end_row();
+          ui.label("State:");
+          ui.horizontal(|ui| {
+            checks.state_ui(ui);
+            ui.label(format!("{:?}", block.header.state_root));
+          });
+          ui.end_row();
+        });
+    });
+    app_event
+  }
+
+  fn event_phase_text(&self, event: &EventInfo, ui: &egui::Ui) -> RichText {
+    let phase = RichText::new(format!("{:?}", event.phase));
+    let highlight = matches!(
+      event.phase,
+      Phase::ApplyExtrinsic(idx) if Some(idx) == self.highlight_extrinsic
+    );
+    if highlight {
+      phase.strong().color(ui.visuals().warn_fg_color)
+    } else {
+      phase
+    }
+  }
+
+  /// Events as plain rows, when the events table doesn't fit.
+  fn block_events_fallback_ui(&mut self, ui: &mut egui::Ui, block: &BlockInfo, num_rows: usize) {
+    let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
+    ScrollArea::vertical().show_rows(ui, text_height, num_rows, |ui, row_range| {
+      // HACK(egui): `show_rows` can give an invalid row range, clamp it.
+      let end = row_range.end.min(num_rows);
+      let start = row_range.start.min(end);
+      for event in &block.events[start..end] {
+        ui.horizontal(|ui| {
+          ui.label(self.event_phase_text(event, ui));
+          ui.colored_label(
+            pallet_color(&event.name, ui.visuals().dark_mode),
+            event.name.as_ref(),
+          );
+          if ui.small_button("🔍").on_hover_text("Show value").clicked() {
+            self.open_event = Some(event.number);
+          }
+        });
+      }
+    });
+  }
+
+  /// Events with the leftmost columns frozen, the values aren't truncated.
+  fn block_events_frozen_ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    block: &BlockInfo,
+    num_rows: usize,
+    frozen: usize,
+  ) {
+    let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
+    FrozenTable::new("block_events", frozen, text_height)
+      .column("Phase", 150.0)
+      .column("Name", 150.0)
+      .column("Value", 100.0)
+      .show(ui, num_rows, |ui, row, col| {
+        let Some(event) = block.events.get(row) else {
+          return;
+        };
+        match col {
+          0 => {
+            ui.label(self.event_phase_text(event, ui));
+          }
+          1 => {
+            let color = pallet_color(&event.name, ui.visuals().dark_mode);
+            ui.colored_label(color, event.name.as_ref());
+          }
+          _ => {
+            if ui.small_button("🔍").on_hover_text("Show value").clicked() {
+              self.open_event = Some(event.number);
+            }
+            ui.label(format!("{:?}", serde_json::to_string(&event.value)));
+          }
+        }
+      });
+  }
+
+  fn block_events_ui(&mut self, ui: &mut egui::Ui, settings: &Settings, block: &BlockInfo) {
     let width = ui.available_width();
     ui.set_width(width);
+    // Huge blocks only show their first events, until the user asks for all of them.
+    let total = block.events.len();
+    let num_rows = if self.show_all_events {
+      total
+    } else {
+      total.min(MAX_SHOWN_EVENTS)
+    };
+    if num_rows < total {
+      ui.horizontal(|ui| {
+        ui.colored_label(
+          ui.visuals().warn_fg_color,
+          format!("Showing the first {num_rows} of {total} events."),
+        );
+        if ui.button("Show all").clicked() {
+          self.show_all_events = true;
+        }
+      });
+    }
+    if settings.frozen_columns > 0 {
+      self.block_events_frozen_ui(ui, block, num_rows, settings.frozen_columns);
+      return;
+    }
+    if !table_fits(ui, EVENTS_TABLE_MIN_WIDTH) {
+      self.block_events_fallback_ui(ui, block, num_rows);
+      return;
+    }
     let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
     TableBuilder::new(ui)
       .striped(true)
@@ -625,44 +3292,393 @@ impl BlockDetailsApp {
         });
       })
       .body(|body| {
-        let num_rows = block.events.len();
         body.rows(text_height, num_rows, |row_index, mut row| {
           if let Some(event) = block.events.get(row_index) {
             row.col(|ui| {
-              ui.label(format!("{:?}", event.phase));
+              ui.label(self.event_phase_text(event, ui));
             });
             row.col(|ui| {
-              ui.label(event.name.to_string());
+              let color = pallet_color(&event.name, ui.visuals().dark_mode);
+              ui.colored_label(color, event.name.as_ref());
             });
             row.col(|ui| {
+              if ui.small_button("🔍").on_hover_text("Show value").clicked() {
+                self.open_event = Some(event.number);
+              }
               ui.label(format!("{:?}", serde_json::to_string(&event.value)));
             });
           }
-        })
-      });
+        })
+      });
+  }
+
+  fn pending_block_ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    backend: &BackendState,
+    number: BlockNumber,
+  ) -> Option<SubAppEvent> {
+    if let Some(block) = backend.blocks.get(&number) {
+      // The block has been produced, show it.
+      self.pending_number = None;
+      return Some(SubAppEvent::BlockDetails(block.hash));
+    }
+    if number <= backend.best_block {
+      ui.label(format!("Loading block #{number}..."));
+    } else {
+      ui.label(format!("Waiting for block #{number} to be produced..."));
+    }
+    if ui.button("Cancel").clicked() {
+      self.pending_number = None;
+    }
+    None
+  }
+
+  fn block_nav_ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    backend: &BackendState,
+    block: &BlockInfo,
+  ) -> Option<SubAppEvent> {
+    let mut app_event = None;
+    let number = block.number();
+    ui.horizontal(|ui| {
+      if ui.add_enabled(number > 0, Button::new("⏴ Prev")).clicked() {
+        app_event = Some(SubAppEvent::BlockDetails(block.header.parent_hash));
+      }
+      let next = number + 1;
+      let next_block = backend.blocks.get(&next);
+      // Only wait for blocks that haven't been produced yet.
+      let can_wait = next > backend.best_block;
+      if ui
+        .add_enabled(next_block.is_some() || can_wait, Button::new("Next ⏵"))
+        .on_disabled_hover_text("Block not loaded")
+        .clicked()
+      {
+        match next_block {
+          Some(block) => {
+            app_event = Some(SubAppEvent::BlockDetails(block.hash));
+          }
+          None => {
+            self.pending_number = Some(next);
+          }
+        }
+      }
+    });
+    app_event
+  }
+
+  fn raw_header_ui(&self, ui: &mut egui::Ui, block: &BlockInfo) {
+    let header = &block.header;
+    egui::CollapsingHeader::new("Raw header").show(ui, |ui| {
+      let fields = [
+        ("Header", header.encode()),
+        ("Parent", header.parent_hash.encode()),
+        ("Number", Compact(header.number).encode()),
+        ("State", header.state_root.encode()),
+        ("Extrinsics", header.extrinsics_root.encode()),
+        ("Digest", header.digest.encode()),
+      ];
+      egui::Grid::new("raw_header_grid")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+          for (name, encoded) in fields {
+            ui.label(format!("{name} ({} bytes)", encoded.len()));
+            ui.add(
+              egui::Label::new(RichText::new(format!("0x{}", hex::encode(encoded))).monospace())
+                .wrap(true),
+            );
+            ui.end_row();
+          }
+        });
+    });
+  }
+
+  fn justification_ui(&self, ui: &mut egui::Ui, backend: &BackendState, block: &BlockInfo) {
+    egui::CollapsingHeader::new("Justification").show(ui, |ui| {
+      match backend.justifications.get(&block.hash) {
+        Some(Some(justification)) => {
+          ui.label(format!(
+            "GRANDPA round {}: {} precommits for block #{} ({:?})",
+            justification.round,
+            justification.precommits,
+            justification.target_number,
+            justification.target_hash
+          ));
+        }
+        Some(None) => {
+          ui.label("The node has no justification stored for this block.");
+        }
+        None => {
+          if ui.button("Load justification").clicked() {
+            backend.get_justification(block.hash);
+          }
+        }
+      }
+    });
+  }
+
+  /// Developer action decoding the block's events again, showing each step: the raw event
+  /// record, the serialized runtime event and the value shown in the events table.
+  fn event_trace_ui(&mut self, ui: &mut egui::Ui, backend: &BackendState, block: &BlockInfo) {
+    egui::CollapsingHeader::new("Decoding trace").show(ui, |ui| {
+      let requested = self.trace_requested == Some(block.hash);
+      let traces = backend.event_traces.get(&block.hash);
+      ui.horizontal(|ui| {
+        let label = if traces.is_some() {
+          "⟳ Trace again"
+        } else {
+          "🐞 Trace decoding"
+        };
+        if ui
+          .button(label)
+          .on_hover_text("Decode the block's events again from the raw storage, one at a time")
+          .clicked()
+        {
+          self.trace_requested = Some(block.hash);
+          backend.trace_events(block);
+        }
+        if requested && traces.is_none() {
+          ui.spinner();
+        }
+      });
+      let traces = match traces {
+        Some(Ok(traces)) => traces,
+        Some(Err(err)) => {
+          ui.colored_label(ui.visuals().error_fg_color, format!("Trace failed: {err}"));
+          return;
+        }
+        None => return,
+      };
+      let large_nodes = backend.settings.large_json_nodes;
+      let depth = backend.settings.json_expand_depth;
+      ScrollArea::vertical()
+        .id_source("event_traces")
+        .max_height(400.0)
+        .show(ui, |ui| {
+          for trace in traces {
+            let name = trace.name.as_deref().unwrap_or("<failed to decode>");
+            let mut title = RichText::new(format!("#{} {name}", trace.index));
+            if trace.error.is_some() {
+              title = title.color(ui.visuals().warn_fg_color);
+            }
+            egui::CollapsingHeader::new(title)
+              .id_source(("event_trace", trace.index))
+              .show(ui, |ui| {
+                if let Some(err) = &trace.error {
+                  ui.colored_label(ui.visuals().warn_fg_color, err);
+                }
+                if let Some(phase) = &trace.phase {
+                  ui.label(format!("Phase: {phase:?}"));
+                }
+                ui.horizontal(|ui| {
+                  ui.label(format!(
+                    "Raw ({} bytes):",
+                    trace.raw.len().saturating_sub(2) / 2
+                  ));
+                  let button = ui.small_button("📋").on_hover_text("Copy");
+                  if button.clicked() {
+                    ui.output_mut(|o| o.copied_text = trace.raw.clone());
+                  }
+                  copied_ui(ui, &button, button.clicked());
+                });
+                let mut raw = trace.raw.as_str();
+                ui.add(
+                  TextEdit::multiline(&mut raw)
+                    .code_editor()
+                    .desired_rows(2)
+                    .desired_width(f32::INFINITY),
+                );
+                json_tree_ui(
+                  ui,
+                  ("serialized", trace.index),
+                  "Serialized event",
+                  &trace.serialized,
+                  large_nodes,
+                  depth,
+                );
+                json_tree_ui(
+                  ui,
+                  ("value", trace.index),
+                  "Value",
+                  &trace.value,
+                  large_nodes,
+                  depth,
+                );
+              });
+          }
+        });
+    });
+  }
+
+  fn event_value_ui(
+    &mut self,
+    ctx: &egui::Context,
+    backend: &BackendState,
+    block: &BlockInfo,
+  ) -> Option<SubAppEvent> {
+    let event = self
+      .open_event
+      .and_then(|number| block.events.get(number as usize))?;
+    let large_nodes = backend.settings.large_json_nodes;
+    let depth = backend.settings.json_expand_depth;
+    let mut app_event = None;
+    let mut clicked_block = None;
+    let mut open = true;
+    egui::Window::new(format!(
+      "Event {}-{}: {}",
+      event.block, event.number, event.name
+    ))
+    .id(Id::new(("event_value", block.hash)))
+    .open(&mut open)
+    .vscroll(true)
+    .show(ctx, |ui| {
+      let id = (event.block, event.number);
+      if let Some(size) = event.elided {
+        let key = (block.hash, event.number);
+        match backend.event_values.get(&key) {
+          Some(Some(value)) => {
+            clicked_block = json_tree_ui(ui, id, "value", value, large_nodes, depth);
+          }
+          Some(None) => {
+            ui.colored_label(
+              ui.visuals().warn_fg_color,
+              format!("Failed to load the value ({size} bytes)"),
+            );
+          }
+          None => {
+            if self.value_requested != Some(key) {
+              self.value_requested = Some(key);
+              backend.get_event_value(block.hash, event.number);
+            }
+            ui.horizontal(|ui| {
+              ui.spinner();
+              ui.label(format!("Loading the full value ({size} bytes)..."));
+            });
+          }
+        }
+        return;
+      }
+      let ss58_prefix = backend.ss58_prefix();
+      let tz = backend.settings.time_zone;
+      let amounts = backend.settings.amount_format;
+      if staking_event_ui(ui, event, ss58_prefix, amounts, &mut app_event)
+        || identity_event_ui(ui, event, ss58_prefix, tz, &mut app_event)
+      {
+        egui::CollapsingHeader::new("Raw value").show(ui, |ui| {
+          clicked_block = json_tree_ui(ui, id, "value", &event.value, large_nodes, depth);
+        });
+      } else {
+        clicked_block = json_tree_ui(ui, id, "value", &event.value, large_nodes, depth);
+      }
+    });
+    if !open {
+      self.open_event = None;
+    }
+    if let Some(number) = clicked_block {
+      match backend.blocks.get(&number) {
+        Some(block) => {
+          app_event = Some(SubAppEvent::BlockDetails(block.hash));
+        }
+        None => {
+          // Load the block, or wait for it to be produced (e.g. scheduled calls).
+          if number <= backend.best_block {
+            backend.get_block_range(number, 1);
+          }
+          self.open_event = None;
+          self.pending_number = Some(number);
+        }
+      }
+    }
+    app_event
   }
 
-  fn show_block_ui(&self, ui: &mut egui::Ui, block: &BlockInfo) -> Option<SubAppEvent> {
+  fn show_block_ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    backend: &BackendState,
+    block: &BlockInfo,
+  ) -> Option<SubAppEvent> {
     let mut app_event = None;
     let width = ui.available_width();
     ui.set_width(width);
     let height = ui.available_height();
     ui.set_height(height);
+    ui.horizontal(|ui| {
+      let button = ui
+        .button("📋 Copy all as table")
+        .on_hover_text("Copy the header and events as tab separated values");
+      if button.clicked() {
+        let tsv = block_tsv(block, &backend.settings);
+        ui.output_mut(|o| o.copied_text = tsv);
+      }
+      copied_ui(ui, &button, button.clicked());
+      if block.from_archive {
+        ui.weak(format!("Loaded from archive node: {}", backend.archive_url));
+      }
+    });
+    if let Some(err) = &block.events_error {
+      ui.colored_label(
+        ui.visuals().warn_fg_color,
+        format!("Events unavailable: {err}"),
+      );
+    }
+    if block.header_only {
+      // Preloaded without events, load them now that the block is open.
+      if self.events_requested != Some(block.hash) {
+        self.events_requested = Some(block.hash);
+        backend.get_block_info(block.hash);
+      }
+      ui.weak("Loading events...");
+    }
+    ui.push_id("Raw Header", |ui| {
+      self.raw_header_ui(ui, block);
+    });
+    ui.push_id("Justification", |ui| {
+      self.justification_ui(ui, backend, block);
+    });
+    if backend.raw_rpc_ui(ui, RpcSubject::Block(block.hash)) {
+      backend.get_block_info(block.hash);
+    }
+    ui.push_id("Decoding trace", |ui| {
+      self.event_trace_ui(ui, backend, block);
+    });
+    ui.push_id("Calls", |ui| {
+      let index = block_calls_ui(
+        ui,
+        block,
+        self.highlight_extrinsic,
+        &mut self.group_by_signer,
+        backend.ss58_prefix(),
+        &backend.settings,
+      );
+      if let Some(index) = index {
+        self.highlight_extrinsic = Some(index);
+      }
+    });
     StripBuilder::new(ui)
       .size(Size::initial(60.0).at_least(40.0)) // Block header
-      .size(Size::remainder()) // Extrinsics.
+      .size(Size::remainder()) // Events.
       .vertical(|mut strip| {
         strip.cell(|ui| {
           ui.push_id("Block Header", |ui| {
-            app_event = self.block_header_ui(ui, block);
+            let parent = block
+              .number()
+              .checked_sub(1)
+              .and_then(|number| backend.blocks.get(&number));
+            app_event = self.block_header_ui(ui, &backend.settings, block, parent);
           });
         });
         strip.cell(|ui| {
-          ui.push_id("Block Extrinsics", |ui| {
-            self.block_extrinsics_ui(ui, block);
+          ui.push_id("Block Events", |ui| {
+            self.block_events_ui(ui, &backend.settings, block);
           });
         });
       });
+    if let Some(event) = self.event_value_ui(ui.ctx(), backend, block) {
+      app_event = Some(event);
+    }
     app_event
   }
 }
@@ -682,23 +3698,102 @@ impl SubApp for BlockDetailsApp {
     ctx: &egui::Context,
     anchor: &str,
   ) -> Option<SubAppEvent> {
+    // Escape closes the event value window, then goes back to the explorer.
+    let escape = ctx.input(|i| i.key_pressed(Key::Escape));
+    if escape && ctx.memory(|m| m.focus().is_none()) {
+      if self.view.open_event.is_some() {
+        self.view.open_event = None;
+      } else {
+        return Some(SubAppEvent::OpenApp("explorer".into()));
+      }
+    }
+
+    // Keep the shown blocks cached, when older blocks are trimmed.
+    let selected = match &self.selected_block {
+      SelectedBlock::Hash(hash) => backend.block_by_hash(hash).map(|block| block.number()),
+      SelectedBlock::Number(number) => Some(*number),
+      SelectedBlock::Best | SelectedBlock::None => None,
+    };
+    let windows = self
+      .windows
+      .iter()
+      .flat_map(|window| {
+        let number = backend
+          .block_by_hash(&window.hash)
+          .map(|block| block.number());
+        number.into_iter().chain(window.view.pending_number)
+      })
+      .collect::<Vec<_>>();
+    for number in selected
+      .into_iter()
+      .chain(self.view.pending_number)
+      .chain(windows)
+    {
+      backend.keep_blocks(number..number + 1);
+    }
+
+    let backend = &*backend;
     let res = self.parse_anchor_and_load_block(backend, anchor);
 
-    let mut app_event = None;
-    egui::CentralPanel::default().show(ctx, |ui| match res {
-      Ok(block) => {
-        if let Some(block) = block {
-          app_event = self.show_block_ui(ui, block);
-        } else {
-          ui.label("Loading block...".to_string());
-        }
+    let mut app_event = self.windows_ui(ctx, backend);
+    egui::CentralPanel::default().show(ctx, |ui| {
+      if let Some(number) = self.view.pending_number {
+        app_event = self.view.pending_block_ui(ui, backend, number);
+        return;
       }
-      Err(err) => {
-        ui.label(format!("Failed: {err:?}"));
+      match res {
+        Ok(block) => {
+          if let Some(block) = block {
+            self.visit(block);
+            if let Some(event) = self.breadcrumb_ui(ui) {
+              app_event = Some(event);
+            }
+            ui.horizontal(|ui| {
+              if ui
+                .button("⧉ Open in window")
+                .on_hover_text("Open the block in its own window, to compare it with other blocks")
+                .clicked()
+              {
+                self.open_window(ui.ctx(), block.hash);
+              }
+              if !self.windows.is_empty() && ui.button("Close windows").clicked() {
+                self.windows.clear();
+              }
+            });
+            if let Some(event) = self.view.block_nav_ui(ui, backend, block) {
+              app_event = Some(event);
+            }
+            if let Some(event) = self.view.show_block_ui(ui, backend, block) {
+              app_event = Some(event);
+            }
+          } else if matches!(self.selected_block, SelectedBlock::None) {
+            ui.label("No block selected.  Pick a block from the explorer, or paste a block hash or number in the navbar.");
+            if ui.button("Show best block").clicked() {
+              app_event = Some(SubAppEvent::OpenApp(self.anchor().to_string()));
+            }
+          } else {
+            ui.label("Loading block...".to_string());
+          }
+        }
+        Err(err) => {
+          ui.label(format!("Failed: {err:?}"));
+        }
       }
     });
     app_event
   }
+
+  fn reset(&mut self) {
+    *self = Default::default();
+  }
+}
+
+/// Search running in the backend.
+#[derive(Clone, Copy)]
+enum Lookup {
+  Extrinsic(TxHash),
+  /// Block closest to a time, in milliseconds since the unix epoch.
+  Time(u64),
 }
 
 /// Sub-Apps.
@@ -709,6 +3804,30 @@ pub struct State {
   current_anchor: String,
   chain_info: ChainInfoApp,
   block_details: BlockDetailsApp,
+  dashboard: DashboardApp,
+  account: AccountApp,
+  calls: CallIndexApp,
+  constants: ConstantsApp,
+
+  /// Pasted identifier waiting for the user to pick how to view it.
+  #[serde(skip)]
+  inspect: Option<(String, Vec<JumpTarget>)>,
+
+  /// Search running in the backend.
+  #[serde(skip)]
+  lookup: Option<Lookup>,
+
+  /// Panic messages of crashed sub-apps, keyed by the sub-app's anchor.
+  #[serde(skip)]
+  app_errors: HashMap<String, String>,
+
+  /// Visited anchors, newest last.  Only used by native builds, the web uses the browser's
+  /// history.
+  #[serde(skip)]
+  back: Vec<String>,
+  /// Anchors gone back from, newest last.
+  #[serde(skip)]
+  forward: Vec<String>,
 }
 
 impl State {
@@ -716,6 +3835,10 @@ impl State {
     let apps = vec![
       &mut self.chain_info as &mut dyn SubApp,
       &mut self.block_details as &mut dyn SubApp,
+      &mut self.dashboard as &mut dyn SubApp,
+      &mut self.account as &mut dyn SubApp,
+      &mut self.calls as &mut dyn SubApp,
+      &mut self.constants as &mut dyn SubApp,
     ];
 
     apps.into_iter()
@@ -725,7 +3848,14 @@ impl State {
     if self.current_anchor == anchor {
       return;
     }
-    self.current_anchor = anchor.to_string();
+    let previous = std::mem::replace(&mut self.current_anchor, anchor.to_string());
+    if !previous.is_empty() {
+      self.back.push(previous);
+      if self.back.len() > MAX_HISTORY {
+        self.back.remove(0);
+      }
+    }
+    self.forward.clear();
     if frame.is_web() {
       ctx.output_mut(|o| {
         o.open_url = Some(egui::output::OpenUrl::same_tab(format!("#{}", anchor)));
@@ -733,21 +3863,341 @@ impl State {
     }
   }
 
+  fn can_go_back(&self, frame: &eframe::Frame) -> bool {
+    frame.is_web() || !self.back.is_empty()
+  }
+
+  fn can_go_forward(&self, frame: &eframe::Frame) -> bool {
+    frame.is_web() || !self.forward.is_empty()
+  }
+
+  /// Go back or forward in the navigation history.  On the web this moves through the browser's
+  /// history, which changes the url anchor.
+  fn navigate_history(&mut self, back: bool, frame: &eframe::Frame) {
+    if frame.is_web() {
+      #[cfg(target_arch = "wasm32")]
+      if let Some(history) = web_sys::window().and_then(|window| window.history().ok()) {
+        let res = if back {
+          history.back()
+        } else {
+          history.forward()
+        };
+        if let Err(err) = res {
+          log::error!("Failed to navigate the browser history: {err:?}");
+        }
+      }
+      return;
+    }
+    let (from, to) = if back {
+      (&mut self.back, &mut self.forward)
+    } else {
+      (&mut self.forward, &mut self.back)
+    };
+    if let Some(anchor) = from.pop() {
+      to.push(std::mem::replace(&mut self.current_anchor, anchor));
+    }
+  }
+
+  fn is_known_anchor(&mut self, anchor: &str) -> bool {
+    self.apps().any(|app| app.match_anchor(anchor))
+  }
+
+  /// The view opened when there is no anchor, the first sub-app unless the user chose another.
+  fn home_anchor(&mut self, settings: &Settings) -> String {
+    let home = &settings.home_anchor;
+    if !home.is_empty() && self.is_known_anchor(home) {
+      return home.clone();
+    }
+    self.apps().next().unwrap().anchor().to_string()
+  }
+
+  /// Check an anchor from the url.  Sub-app anchors and identifiers that `inspect` can open are
+  /// kept, anything else falls back to the home view.
+  #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+  fn sanitize_anchor(&mut self, anchor: &str, settings: &Settings) -> String {
+    let valid = anchor.len() <= MAX_ANCHOR_LEN
+      && (self.is_known_anchor(anchor)
+        || ViewState::from_code(anchor).is_some()
+        || !JumpTarget::classify(anchor).is_empty());
+    if valid {
+      return anchor.to_string();
+    }
+    log::warn!(
+      "Ignoring invalid url anchor: {:?}",
+      anchor.chars().take(100).collect::<String>()
+    );
+    self.home_anchor(settings)
+  }
+
+  /// Open the view for a pasted identifier, asking the user how to view it when it is
+  /// ambiguous.  Returns `false` if `input` isn't something that can be opened.
+  fn inspect(
+    &mut self,
+    backend: &mut BackendState,
+    input: &str,
+    ctx: &egui::Context,
+    frame: &mut eframe::Frame,
+  ) -> bool {
+    if let Some(view) = ViewState::from_code(input) {
+      self.apply_view_state(backend, view, ctx, frame);
+      return true;
+    }
+    let targets = JumpTarget::classify(input);
+    match targets.as_slice() {
+      [] => false,
+      [target] => self.open_target(backend, *target, ctx, frame),
+      _ => {
+        self.inspect = Some((input.trim().to_string(), targets));
+        true
+      }
+    }
+  }
+
+  fn open_target(
+    &mut self,
+    backend: &mut BackendState,
+    target: JumpTarget,
+    ctx: &egui::Context,
+    frame: &mut eframe::Frame,
+  ) -> bool {
+    match target {
+      JumpTarget::Number(number) => match backend.block(number) {
+        Some(block) => self.open_block(block.hash, ctx, frame),
+        // The block details view loads the block by number.
+        None => self.open_anchor(&format!("block_details/{number}"), ctx, frame),
+      },
+      JumpTarget::Hash(hash) => self.open_block(hash, ctx, frame),
+      JumpTarget::Extrinsic(hash) => {
+        backend.find_extrinsic(hash);
+        self.lookup = Some(Lookup::Extrinsic(hash));
+        // The lookup opens the block once found, don't inspect the anchor again meanwhile.
+        self.leave_unknown_anchor(backend, ctx, frame);
+      }
+      JumpTarget::Account(account) => self.open_anchor(&account_anchor(&account), ctx, frame),
+    }
+    true
+  }
+
+  /// Go to the home view from an anchor no sub-app knows, e.g. a pasted identifier.
+  fn leave_unknown_anchor(
+    &mut self,
+    backend: &BackendState,
+    ctx: &egui::Context,
+    frame: &mut eframe::Frame,
+  ) {
+    let anchor = self.current_anchor.clone();
+    if !self.is_known_anchor(&anchor) {
+      let anchor = self.home_anchor(&backend.settings);
+      self.open_anchor(&anchor, ctx, frame);
+    }
+  }
+
+  /// "Inspect as" chooser for ambiguous identifiers.
+  fn inspect_ui(
+    &mut self,
+    backend: &mut BackendState,
+    ctx: &egui::Context,
+    frame: &mut eframe::Frame,
+  ) {
+    let Some((input, targets)) = &self.inspect else {
+      return;
+    };
+    let mut picked = None;
+    let mut cancel = false;
+    egui::Window::new("Inspect as")
+      .collapsible(false)
+      .resizable(false)
+      .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+      .show(ctx, |ui| {
+        ui.label(RichText::new(input).monospace());
+        ui.horizontal(|ui| {
+          for target in targets {
+            if ui.button(target.label()).clicked() {
+              picked = Some(*target);
+            }
+          }
+          cancel = ui.button("Cancel").clicked();
+        });
+      });
+    if let Some(target) = picked {
+      self.inspect = None;
+      self.open_target(backend, target, ctx, frame);
+    } else if cancel {
+      self.inspect = None;
+      self.leave_unknown_anchor(backend, ctx, frame);
+    }
+  }
+
+  /// Progress of a backend search.  Opens the result when it's found.
+  fn lookup_ui(&mut self, backend: &BackendState, ctx: &egui::Context, frame: &mut eframe::Frame) {
+    let Some(lookup) = self.lookup else {
+      return;
+    };
+    // `None` while searching, `Some(None)` if nothing was found.
+    let (title, subject, found, not_found) = match lookup {
+      Lookup::Extrinsic(hash) => (
+        "Find extrinsic",
+        format!("{hash:?}"),
+        backend.found_extrinsics.get(&hash).map(|location| {
+          location.map(|(block, index)| format!("block_details/{block:?}/{index}"))
+        }),
+        format!("Not found in the last {FIND_EXTRINSIC_BLOCKS} blocks."),
+      ),
+      Lookup::Time(time) => (
+        "Find block by time",
+        format_time(time, backend.settings.time_zone),
+        backend
+          .blocks_at_time
+          .get(&time)
+          .map(|hash| hash.map(|hash| format!("block_details/{hash:?}"))),
+        "No block found.".to_string(),
+      ),
+    };
+    if let Some(Some(anchor)) = found {
+      self.lookup = None;
+      self.open_anchor(&anchor, ctx, frame);
+      return;
+    }
+    let mut close = false;
+    egui::Window::new(title)
+      .collapsible(false)
+      .resizable(false)
+      .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+      .show(ctx, |ui| {
+        ui.label(RichText::new(subject).monospace());
+        if found.is_some() {
+          ui.label(not_found);
+          close = ui.button("Close").clicked();
+        } else {
+          ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("Searching...");
+          });
+          close = ui.button("Cancel").clicked();
+        }
+      });
+    if close {
+      self.lookup = None;
+    }
+  }
+
+  fn find_block_by_time(&mut self, backend: &mut BackendState, time: u64) {
+    backend.find_block_by_time(time);
+    self.lookup = Some(Lookup::Time(time));
+  }
+
   fn update(&mut self, backend: &mut BackendState, ctx: &egui::Context, frame: &mut eframe::Frame) {
     let anchor = self.current_anchor.clone();
+    // An anchor that no sub-app knows might be a pasted identifier, e.g. `#0x...`.
+    if self.inspect.is_none() && !self.is_known_anchor(&anchor) {
+      self.inspect(backend, &anchor, ctx, frame);
+    }
+    self.inspect_ui(backend, ctx, frame);
+    self.lookup_ui(backend, ctx, frame);
     let mut app_event = None;
+    let mut app_errors = std::mem::take(&mut self.app_errors);
     for app in self.apps() {
       if app.match_anchor(&anchor) {
-        if let Some(event) = app.update(backend, ctx, &anchor) {
-          app_event = Some(event);
-          break;
+        let key = app.anchor().to_string();
+        if let Some(err) = app_errors.get(&key) {
+          if app_error_ui(ctx, app.name(), err) {
+            app.reset();
+            app_errors.remove(&key);
+          }
+          continue;
+        }
+        // Don't let a panic in one sub-app take down the whole app.
+        // NOTE: wasm builds abort on panic, so this only helps native builds.
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+          app.update(backend, ctx, &anchor)
+        }));
+        match res {
+          Ok(Some(event)) => {
+            app_event = Some(event);
+            break;
+          }
+          Ok(None) => (),
+          Err(panic) => {
+            let err = panic_message(panic);
+            log::error!("Sub-app {:?} crashed: {err}", app.name());
+            backend.push_error(format!("{} crashed: {err}", app.name()));
+            app_errors.insert(key, err);
+          }
         }
       }
     }
-    if let Some(SubAppEvent::BlockDetails(hash)) = app_event {
-      let anchor = format!("block_details/{:?}", hash);
-      self.open_anchor(&anchor, ctx, frame);
+    self.app_errors = app_errors;
+    match app_event {
+      Some(SubAppEvent::BlockDetails(hash)) => {
+        self.open_block(hash, ctx, frame);
+      }
+      Some(SubAppEvent::OpenApp(anchor)) => {
+        self.open_anchor(&anchor, ctx, frame);
+      }
+      None => (),
+    }
+  }
+
+  fn open_block(&mut self, hash: BlockHash, ctx: &egui::Context, frame: &mut eframe::Frame) {
+    let anchor = format!("block_details/{:?}", hash);
+    self.open_anchor(&anchor, ctx, frame);
+  }
+
+  /// The open view, the events feed filters and the pins, for sharing.
+  fn view_state(&self, backend: &BackendState) -> ViewState {
+    ViewState {
+      anchor: self.current_anchor.clone(),
+      pallets: self.chain_info.pallet_chips.iter().cloned().collect(),
+      group_events: self.chain_info.group_events,
+      range_start: self.chain_info.range_start,
+      pinned_blocks: backend.pinned_blocks.iter().copied().collect(),
+      pinned_events: backend.pinned_events.iter().copied().collect(),
+    }
+  }
+
+  /// Open a shared view.  The shared pins are added to the user's own pins.
+  fn apply_view_state(
+    &mut self,
+    backend: &mut BackendState,
+    view: ViewState,
+    ctx: &egui::Context,
+    frame: &mut eframe::Frame,
+  ) {
+    log::info!("Open shared view: {:?}", view.anchor);
+    let chain_info = &mut self.chain_info;
+    chain_info.pallet_chips = view.pallets.into_iter().collect();
+    chain_info.group_events = view.group_events;
+    if chain_info.range_start != view.range_start {
+      if chain_info.range_start.is_some() {
+        backend.cancel_block_range();
+      }
+      chain_info.range_start = view.range_start;
+      chain_info.range_requested = false;
+    }
+    chain_info.reset_scroll = true;
+    for hash in view.pinned_blocks {
+      // Pinned blocks from another session have to be loaded.
+      if backend.block_by_hash(&hash).is_none() {
+        backend.get_block_info(hash);
+      }
+      backend.pinned_blocks.insert(hash);
+    }
+    for (hash, index) in view.pinned_events {
+      if backend.block_by_hash(&hash).is_none() {
+        backend.get_block_info(hash);
+      }
+      backend.pinned_events.insert((hash, index));
     }
+    backend.need_save = true;
+    // A shared anchor that no sub-app knows falls back to the home view.
+    let anchor = if self.is_known_anchor(&view.anchor) {
+      view.anchor
+    } else {
+      self.home_anchor(&backend.settings)
+    };
+    // Force the anchor to be applied, the current anchor might be the view state code.
+    self.current_anchor.clear();
+    self.open_anchor(&anchor, ctx, frame);
   }
 }
 
@@ -758,6 +4208,28 @@ pub struct PolymeshApp {
   state: State,
 
   backend: BackendState,
+
+  #[serde(skip)]
+  jump_to: String,
+
+  /// Show the jump to time window.
+  #[serde(skip)]
+  jump_time_open: bool,
+  #[serde(skip)]
+  jump_date: Option<chrono::NaiveDate>,
+  #[serde(skip)]
+  jump_hour: u32,
+  #[serde(skip)]
+  jump_minute: u32,
+  /// The `view` url parameter was checked (web only).
+  #[serde(skip)]
+  view_param_checked: bool,
+  /// Last url anchor seen, without the `#` (web only).
+  #[serde(skip)]
+  location_hash: String,
+  /// Time of the last save.
+  #[serde(skip)]
+  last_save: f64,
 }
 
 impl PolymeshApp {
@@ -770,16 +4242,30 @@ impl PolymeshApp {
     };
 
     cc.egui_ctx.set_visuals(egui::Visuals::dark());
+    app.backend.settings.apply_accent(&cc.egui_ctx);
+    // Start at the home view instead of the last view.  On the web an anchor in the url still
+    // takes precedence.
+    if !app.backend.settings.home_anchor.is_empty() {
+      app.state.current_anchor.clear();
+    }
 
-    app.backend.connect();
+    if app.backend.settings.connect_on_startup {
+      app.backend.connect();
+    } else {
+      // Wait for the user to click Connect.
+      app.backend.status = ConnectionStatus::Disconnected;
+    }
 
     app
   }
 
-  fn save_changes(&mut self, frame: &mut eframe::Frame) {
-    if !self.backend.check_need_save() {
+  fn save_changes(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+    let now = ctx.input(|i| i.time);
+    // Serializing blocks the UI thread, so bursts of changes (typing, dragging) are saved once.
+    if now - self.last_save < MIN_SAVE_INTERVAL || !self.backend.check_need_save() {
       return;
     }
+    self.last_save = now;
     if let Some(storage) = frame.storage_mut() {
       use eframe::App;
       self.save(storage);
@@ -789,6 +4275,44 @@ impl PolymeshApp {
   fn top_navbar_ui(&mut self, ui: &mut egui::Ui, frame: &mut eframe::Frame) {
     egui::widgets::global_dark_light_mode_switch(ui);
     ui.separator();
+    let back = ui.input_mut(|i| i.consume_key(Modifiers::ALT, Key::ArrowLeft));
+    if ui
+      .add_enabled(self.state.can_go_back(frame), Button::new("⏴"))
+      .on_hover_text("Back (Alt+Left)")
+      .clicked()
+      || back
+    {
+      self.state.navigate_history(true, frame);
+    }
+    let forward = ui.input_mut(|i| i.consume_key(Modifiers::ALT, Key::ArrowRight));
+    if ui
+      .add_enabled(self.state.can_go_forward(frame), Button::new("⏵"))
+      .on_hover_text("Forward (Alt+Right)")
+      .clicked()
+      || forward
+    {
+      self.state.navigate_history(false, frame);
+    }
+    let home = self.state.home_anchor(&self.backend.settings);
+    let resp = ui
+      .button("🏠")
+      .on_hover_text("Home view, right-click to change it");
+    if resp.clicked() {
+      self.state.open_anchor(&home, ui.ctx(), frame);
+    }
+    resp.context_menu(|ui| {
+      if ui.button("Make the current view the home view").clicked() {
+        self.backend.settings.home_anchor = self.state.current_anchor.clone();
+        self.backend.need_save = true;
+        ui.close_menu();
+      }
+      if ui.button("Reset to the first sub-app").clicked() {
+        self.backend.settings.home_anchor.clear();
+        self.backend.need_save = true;
+        ui.close_menu();
+      }
+    });
+    ui.separator();
     ui.toggle_value(&mut self.backend.open, "💻 Backend");
     // Sub-apps
     let current_anchor = self.state.current_anchor.clone();
@@ -805,12 +4329,145 @@ impl PolymeshApp {
     if let Some(new_anchor) = changed {
       self.state.open_anchor(&new_anchor, ui.ctx(), frame);
     }
+    ui.separator();
+    self.jump_box_ui(ui, frame);
+    ui.toggle_value(&mut self.jump_time_open, "🕐")
+      .on_hover_text("Jump to the block at a time");
+    let shortcut = ui.input_mut(|i| i.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::L));
+    let button = ui
+      .button("🔗")
+      .on_hover_text("Copy the view, filters and pins for sharing (Ctrl+Shift+L)");
+    let copied = button.clicked() || shortcut;
+    if copied {
+      self.copy_view_state(ui.ctx());
+    }
+    copied_ui(ui, &button, copied);
+    ui.separator();
+    self.latest_block_ui(ui, frame);
+  }
+
+  /// The best block number, visible from every view.  Click to open the block.
+  fn latest_block_ui(&mut self, ui: &mut egui::Ui, frame: &mut eframe::Frame) {
+    let number = self.backend.best_block;
+    if number == 0 {
+      ui.weak("⛓ No blocks yet");
+      return;
+    }
+    let hash = self.backend.blocks.get(&number).map(|block| block.hash);
+    let resp = ui
+      .add_enabled(
+        hash.is_some(),
+        Button::new(RichText::new(format!("⛓ #{number}")).strong()),
+      )
+      .on_hover_text("Latest block, click to open it")
+      .on_disabled_hover_text("Loading the latest block");
+    if let (true, Some(hash)) = (resp.clicked(), hash) {
+      self.state.open_block(hash, ui.ctx(), frame);
+    }
+  }
+
+  /// Copy the view state code, paste it into the jump box to open the view.  On the web the
+  /// url gets a `view` parameter with the code, so the url can be shared too.
+  fn copy_view_state(&mut self, ctx: &egui::Context) {
+    let view = self.state.view_state(&self.backend);
+    #[cfg(target_arch = "wasm32")]
+    {
+      let url = format!("?view={}#{}", view.to_base58(), self.state.current_anchor);
+      let history = web_sys::window().and_then(|window| window.history().ok());
+      if let Some(history) = history {
+        if let Err(err) =
+          history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url))
+        {
+          log::error!("Failed to update the url: {err:?}");
+        }
+      }
+    }
+    ctx.output_mut(|o| o.copied_text = view.to_code());
+    log::info!("View state copied to the clipboard.");
+  }
+
+  fn jump_time_ui(&mut self, ctx: &egui::Context) {
+    let mut open = self.jump_time_open;
+    let mut find = None;
+    egui::Window::new("Jump to time")
+      .open(&mut open)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        let tz = self.backend.settings.time_zone;
+        let date = self
+          .jump_date
+          .get_or_insert_with(|| chrono::Local::now().date_naive());
+        ui.horizontal(|ui| {
+          ui.add(egui_extras::DatePickerButton::new(date).id_source("jump_date"));
+          ui.add(DragValue::new(&mut self.jump_hour).clamp_range(0..=23));
+          ui.label(":");
+          ui.add(DragValue::new(&mut self.jump_minute).clamp_range(0..=59));
+          ui.weak(match tz {
+            TimeZonePref::Local => "local",
+            TimeZonePref::Utc => "UTC",
+          });
+        });
+        let time = date
+          .and_hms_opt(self.jump_hour, self.jump_minute, 0)
+          .and_then(|time| to_timestamp(time, tz));
+        if ui
+          .add_enabled(time.is_some(), Button::new("Find block"))
+          .clicked()
+        {
+          find = time;
+        }
+      });
+    self.jump_time_open = open;
+    if let Some(time) = find {
+      self.jump_time_open = false;
+      self.state.find_block_by_time(&mut self.backend, time);
+    }
+  }
+
+  fn jump_box_ui(&mut self, ui: &mut egui::Ui, frame: &mut eframe::Frame) {
+    let resp = ui.add(
+      TextEdit::singleline(&mut self.jump_to)
+        .hint_text("Block number, 0x hash, address or view")
+        .desired_width(200.0),
+    );
+    if !self.jump_to.is_empty() {
+      let hint = match JumpTarget::classify(&self.jump_to).as_slice() {
+        [] if ViewState::from_code(&self.jump_to).is_some() => "⏎ shared view".to_string(),
+        [JumpTarget::Number(number)] => format!("⏎ block #{number}"),
+        [JumpTarget::Hash(_)] => "⏎ block hash".to_string(),
+        [JumpTarget::Extrinsic(_)] => "⏎ extrinsic".to_string(),
+        [JumpTarget::Account(_)] => "⏎ account".to_string(),
+        [] => "not a block number, hash or address".to_string(),
+        _ => "⏎ inspect as...".to_string(),
+      };
+      ui.weak(hint);
+    }
+    // `TextEdit` gives up focus on Enter, so other shortcuts keep working.
+    if resp.lost_focus()
+      && ui.input(|i| i.key_pressed(Key::Enter))
+      && self
+        .state
+        .inspect(&mut self.backend, &self.jump_to, ui.ctx(), frame)
+    {
+      self.jump_to.clear();
+    }
   }
 }
 
 impl eframe::App for PolymeshApp {
   /// Called by the frame work to save state before shutdown.
   fn save(&mut self, storage: &mut dyn eframe::Storage) {
+    // Only settings, pins, bookmarks and view state are saved, caches are `#[serde(skip)]`.
+    #[cfg(debug_assertions)]
+    if let Ok(json) = serde_json::to_vec(&*self) {
+      if json.len() > MAX_SAVED_STATE {
+        log::warn!(
+          "Saved state is {} bytes, is a cache missing `#[serde(skip)]`?",
+          json.len()
+        );
+      }
+    }
     eframe::set_value(storage, eframe::APP_KEY, self);
   }
 
@@ -819,20 +4476,44 @@ impl eframe::App for PolymeshApp {
     // Backend updates.
     self.backend.update(ctx, frame);
 
-    self.save_changes(frame);
+    // Re-applied every frame, because the dark/light switch resets the visuals.
+    self.backend.settings.apply_accent(ctx);
+
+    self.save_changes(ctx, frame);
 
     #[cfg(target_arch = "wasm32")]
     {
+      let hash = frame.info().web_info.location.hash;
+      // Only check the url anchor when it changed, e.g. from a link or the back button.
+      if let Some(anchor) = hash
+        .strip_prefix('#')
+        .filter(|anchor| *anchor != self.location_hash)
+      {
+        self.location_hash = anchor.to_owned();
+        let sanitized = self.state.sanitize_anchor(anchor, &self.backend.settings);
+        if sanitized == anchor {
+          self.state.current_anchor = sanitized;
+        } else {
+          // Replace the invalid anchor in the url.
+          self.state.open_anchor(&sanitized, ctx, frame);
+        }
+      }
       let web_info = &frame.info().web_info;
-      if let Some(anchor) = web_info.location.hash.strip_prefix('#') {
-        self.state.current_anchor = anchor.to_owned();
+      // A shared view in the url.
+      if !self.view_param_checked {
+        self.view_param_checked = true;
+        let view = web_info.location.query_map.get("view");
+        if let Some(view) = view.and_then(|data| ViewState::from_base58(data)) {
+          self
+            .state
+            .apply_view_state(&mut self.backend, view, ctx, frame);
+        }
       }
     }
 
-    // Make sure one of the sub-apps is selected.  Default to the first one.
+    // Make sure one of the sub-apps is selected.  Default to the home view.
     if self.state.current_anchor.is_empty() {
-      let anchor = self.state.apps().next().unwrap().anchor();
-      self.state.current_anchor = anchor.to_string();
+      self.state.current_anchor = self.state.home_anchor(&self.backend.settings);
     }
 
     egui::TopBottomPanel::top("top_navbar").show(ctx, |ui| {
@@ -842,10 +4523,25 @@ impl eframe::App for PolymeshApp {
       });
     });
 
+    self.backend.decoding_banner_ui(ctx);
+
     if self.backend.open {
-      egui::SidePanel::left("side_panel").show(ctx, |ui| {
-        self.backend.ui(ui, frame);
-      });
+      let app_event = egui::SidePanel::left("side_panel")
+        .show(ctx, |ui| self.backend.ui(ui, frame))
+        .inner;
+      match app_event {
+        Some(SubAppEvent::BlockDetails(hash)) => {
+          self.state.open_block(hash, ctx, frame);
+        }
+        Some(SubAppEvent::OpenApp(anchor)) => {
+          self.state.open_anchor(&anchor, ctx, frame);
+        }
+        None => (),
+      }
+    }
+
+    if self.jump_time_open {
+      self.jump_time_ui(ctx);
     }
 
     self.state.update(&mut self.backend, ctx, frame);
@@ -0,0 +1,380 @@
+use std::time::Duration;
+
+use super::format::{AmountFormat, TimeZonePref, MAX_SS58_PREFIX, POLYMESH_SS58_PREFIX};
+#[cfg(not(target_arch = "wasm32"))]
+use super::forward::ForwardFormat;
+use super::json::{DEFAULT_JSON_EXPAND_DEPTH, DEFAULT_LARGE_JSON_NODES};
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+use super::metrics::DEFAULT_METRICS_PORT;
+use crate::backend::{
+  Follow, DEFAULT_CONNECT_TIMEOUT, DEFAULT_MAX_EVENT_VALUE, DEFAULT_POLL_INTERVAL,
+  MIN_POLL_INTERVAL,
+};
+
+/// Default time per frame spent applying backend updates.
+const DEFAULT_UPDATE_BUDGET: Duration = Duration::from_millis(8);
+
+/// Accent color presets.
+const ACCENT_PRESETS: &[(&str, [u8; 3])] = &[
+  ("Blue", [0, 122, 204]),
+  ("Green", [46, 160, 67]),
+  ("Orange", [230, 126, 34]),
+  ("Purple", [142, 68, 173]),
+  ("Pink", [214, 51, 132]),
+];
+
+/// User preferences.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+  pub time_zone: TimeZonePref,
+  /// Show the raw integer of amounts next to the token value.
+  pub amount_format: AmountFormat,
+  /// SS58 address prefix to use instead of the chain's.
+  pub ss58_prefix: Option<u16>,
+  /// JSON values with more nodes than this are only rendered when expanded.
+  pub large_json_nodes: usize,
+  /// Number of levels of JSON values that start expanded.
+  pub json_expand_depth: usize,
+  /// Leftmost columns of wide tables kept visible while scrolling horizontally, 0 fits the
+  /// tables to the window instead.
+  pub frozen_columns: usize,
+  /// Accent color for links and selections, `None` uses the theme's colors.
+  pub accent_color: Option<[u8; 3]>,
+  /// Connect to the node when the app starts, instead of waiting for the user to connect.
+  pub connect_on_startup: bool,
+  /// Anchor of the view opened at startup and when there is no anchor, empty opens the first
+  /// sub-app.
+  pub home_anchor: String,
+  /// Seconds between polls for new blocks on HTTP nodes.
+  pub poll_interval_secs: u64,
+  /// Seconds to wait for a connection to the node.
+  pub connect_timeout_secs: u64,
+  /// Milliseconds per frame spent applying backend updates, e.g. while catching up.
+  pub update_budget_ms: u64,
+  /// Capture the raw RPC responses behind loaded blocks and accounts.
+  pub debug_rpc: bool,
+  /// Preload blocks without events, loading them when a block is opened.  New blocks still
+  /// load their events.
+  pub lazy_events: bool,
+  /// Event values larger than this many KiB of JSON are loaded when opened, 0 keeps all values.
+  pub max_event_value_kib: usize,
+  /// `max_event_value_kib` being edited, applied when the edit is committed.
+  #[serde(skip)]
+  max_event_value_input: Option<usize>,
+  /// Which chain heads are followed, applied on the next connect.
+  pub follow: Follow,
+  /// Port of the Prometheus metrics endpoint, `None` disables it.  Only used by native builds
+  /// with the `metrics` feature.
+  pub metrics_port: Option<u16>,
+  /// `metrics_port` being edited, applied when the edit is committed.
+  #[serde(skip)]
+  metrics_port_input: Option<u16>,
+  /// Check GitHub for a newer release of the explorer.  Only used by native builds.
+  pub check_updates: bool,
+  /// Endpoint the decoded events are forwarded to, empty disables forwarding.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub forward_url: String,
+  /// `forward_url` being edited, applied when the edit is committed.
+  #[cfg(not(target_arch = "wasm32"))]
+  #[serde(skip)]
+  forward_url_input: Option<String>,
+  #[cfg(not(target_arch = "wasm32"))]
+  pub forward_format: ForwardFormat,
+}
+
+impl Default for Settings {
+  fn default() -> Self {
+    Self {
+      time_zone: Default::default(),
+      amount_format: Default::default(),
+      ss58_prefix: None,
+      large_json_nodes: DEFAULT_LARGE_JSON_NODES,
+      json_expand_depth: DEFAULT_JSON_EXPAND_DEPTH,
+      frozen_columns: 0,
+      accent_color: None,
+      connect_on_startup: true,
+      home_anchor: String::new(),
+      poll_interval_secs: DEFAULT_POLL_INTERVAL.as_secs(),
+      connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT.as_secs(),
+      update_budget_ms: DEFAULT_UPDATE_BUDGET.as_millis() as u64,
+      debug_rpc: false,
+      lazy_events: false,
+      max_event_value_kib: DEFAULT_MAX_EVENT_VALUE / 1024,
+      max_event_value_input: None,
+      follow: Follow::NewHeads,
+      metrics_port: None,
+      metrics_port_input: None,
+      check_updates: false,
+      #[cfg(not(target_arch = "wasm32"))]
+      forward_url: String::new(),
+      #[cfg(not(target_arch = "wasm32"))]
+      forward_url_input: None,
+      #[cfg(not(target_arch = "wasm32"))]
+      forward_format: ForwardFormat::Loki,
+    }
+  }
+}
+
+impl Settings {
+  /// The SS58 prefix override, ignoring out of range values from old saves.
+  pub fn ss58_prefix(&self) -> Option<u16> {
+    self.ss58_prefix.filter(|prefix| *prefix <= MAX_SS58_PREFIX)
+  }
+
+  /// Interval between polls for new blocks on HTTP nodes.
+  pub fn poll_interval(&self) -> Duration {
+    Duration::from_secs(self.poll_interval_secs).max(MIN_POLL_INTERVAL)
+  }
+
+  /// Largest event value kept with its block, in bytes.
+  pub fn max_event_value(&self) -> usize {
+    self.max_event_value_kib.saturating_mul(1024)
+  }
+
+  /// Time per frame spent applying backend updates.
+  pub fn update_budget(&self) -> Duration {
+    Duration::from_millis(self.update_budget_ms.max(1))
+  }
+
+  /// Time to wait for a connection to the node.
+  pub fn connect_timeout(&self) -> Duration {
+    Duration::from_secs(self.connect_timeout_secs.max(1))
+  }
+
+  /// Apply the accent color on top of the current dark/light theme.
+  pub fn apply_accent(&self, ctx: &egui::Context) {
+    let current = ctx.style().visuals.clone();
+    let theme = if current.dark_mode {
+      egui::Visuals::dark()
+    } else {
+      egui::Visuals::light()
+    };
+    let accent = self
+      .accent_color
+      .map(|[r, g, b]| egui::Color32::from_rgb(r, g, b));
+    let mut visuals = current.clone();
+    visuals.hyperlink_color = accent.unwrap_or(theme.hyperlink_color);
+    visuals.selection.bg_fill = accent.unwrap_or(theme.selection.bg_fill);
+    if visuals != current {
+      ctx.set_visuals(visuals);
+    }
+  }
+
+  /// Settings editor.  Returns `true` if a setting changed.
+  pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+    let mut changed = false;
+    changed |= ui
+      .checkbox(&mut self.connect_on_startup, "Connect on startup")
+      .changed();
+    ui.horizontal(|ui| {
+      ui.label("Home view: ");
+      if self.home_anchor.is_empty() {
+        ui.weak("first sub-app");
+      } else {
+        ui.monospace(format!("#{}", self.home_anchor));
+        if ui.small_button("Reset").clicked() {
+          self.home_anchor.clear();
+          changed = true;
+        }
+      }
+    })
+    .response
+    .on_hover_text("Right-click 🏠 in the navbar to make the current view the home view");
+    ui.horizontal(|ui| {
+      ui.label("Time zone: ");
+      changed |= ui
+        .selectable_value(&mut self.time_zone, TimeZonePref::Local, "Local")
+        .changed();
+      changed |= ui
+        .selectable_value(&mut self.time_zone, TimeZonePref::Utc, "UTC")
+        .changed();
+    });
+    ui.horizontal(|ui| {
+      ui.label("Amounts: ");
+      changed |= ui
+        .selectable_value(&mut self.amount_format, AmountFormat::Token, "POLYX")
+        .changed();
+      changed |= ui
+        .selectable_value(
+          &mut self.amount_format,
+          AmountFormat::TokenAndRaw,
+          "POLYX and raw",
+        )
+        .on_hover_text("Also show the exact integer amount, e.g. 1.500000 POLYX (1500000)")
+        .changed();
+    });
+    ui.horizontal(|ui| {
+      ui.label("Follow: ");
+      changed |= ui
+        .selectable_value(&mut self.follow, Follow::NewHeads, "New heads")
+        .on_hover_text("Show every new block of the best chain, these can be reorged")
+        .changed();
+      changed |= ui
+        .selectable_value(&mut self.follow, Follow::Finalized, "Finalized")
+        .on_hover_text("Only show finalized blocks")
+        .changed();
+    });
+    ui.horizontal(|ui| {
+      let mut overridden = self.ss58_prefix.is_some();
+      if ui
+        .checkbox(&mut overridden, "Override SS58 prefix")
+        .changed()
+      {
+        self.ss58_prefix = overridden.then_some(POLYMESH_SS58_PREFIX);
+        changed = true;
+      }
+      if let Some(prefix) = &mut self.ss58_prefix {
+        changed |= ui
+          .add(egui::DragValue::new(prefix).clamp_range(0..=MAX_SS58_PREFIX))
+          .changed();
+      }
+    });
+    ui.horizontal(|ui| {
+      ui.label("Connect timeout (s): ");
+      changed |= ui
+        .add(egui::DragValue::new(&mut self.connect_timeout_secs).clamp_range(1..=600))
+        .changed();
+    });
+    ui.horizontal(|ui| {
+      ui.label("Update budget (ms/frame): ");
+      changed |= ui
+        .add(egui::DragValue::new(&mut self.update_budget_ms).clamp_range(1..=100))
+        .on_hover_text("Time per frame spent applying updates from the node, higher catches up faster but makes the UI less responsive")
+        .changed();
+    });
+    ui.horizontal(|ui| {
+      ui.label("HTTP poll interval (s): ");
+      changed |= ui
+        .add(
+          egui::DragValue::new(&mut self.poll_interval_secs)
+            .clamp_range(MIN_POLL_INTERVAL.as_secs()..=600),
+        )
+        .on_hover_text("How often HTTP nodes are polled for new blocks")
+        .changed();
+    });
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      changed |= ui
+        .checkbox(&mut self.check_updates, "Check for updates")
+        .on_hover_text("Check the GitHub releases for a newer version, once a day")
+        .changed();
+      ui.horizontal(|ui| {
+        ui.label("Forward events to: ");
+        let input = self
+          .forward_url_input
+          .get_or_insert_with(|| self.forward_url.clone());
+        // Only forward to the url once it is committed, not to every partial url.
+        if ui
+          .add(
+            egui::TextEdit::singleline(input).hint_text("http://localhost:3100/loki/api/v1/push"),
+          )
+          .on_hover_text("Send the decoded events to a log endpoint, empty to disable")
+          .lost_focus()
+        {
+          self.forward_url = input.trim().to_string();
+          changed = true;
+        }
+      });
+      ui.horizontal(|ui| {
+        ui.label("Forward format: ");
+        changed |= ui
+          .selectable_value(&mut self.forward_format, ForwardFormat::Loki, "Loki")
+          .changed();
+        changed |= ui
+          .selectable_value(
+            &mut self.forward_format,
+            ForwardFormat::JsonLines,
+            "JSON lines",
+          )
+          .changed();
+      });
+    }
+    #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+    ui.horizontal(|ui| {
+      let mut enabled = self.metrics_port.is_some();
+      if ui
+        .checkbox(&mut enabled, "Metrics endpoint")
+        .on_hover_text("Serve Prometheus metrics on http://127.0.0.1:<port>/metrics")
+        .changed()
+      {
+        self.metrics_port = enabled.then_some(DEFAULT_METRICS_PORT);
+        self.metrics_port_input = None;
+        changed = true;
+      }
+      if let Some(port) = self.metrics_port {
+        let input = self.metrics_port_input.get_or_insert(port);
+        let resp = ui.add(egui::DragValue::new(input).clamp_range(1024..=u16::MAX));
+        // Only move the server once the port is committed, not to every port dragged past.
+        if (resp.drag_released() || resp.lost_focus()) && *input != port {
+          self.metrics_port = Some(*input);
+          changed = true;
+        }
+      }
+    });
+    changed |= ui
+      .checkbox(&mut self.debug_rpc, "Debug RPC")
+      .on_hover_text("Keep the raw RPC responses of loaded blocks and accounts")
+      .changed();
+    changed |= ui
+      .checkbox(&mut self.lazy_events, "Lazy events")
+      .on_hover_text(
+        "Preload blocks without events, to save bandwidth.  Events load when a block is opened",
+      )
+      .changed();
+    ui.horizontal(|ui| {
+      ui.label("Max event value (KiB): ");
+      let max = self.max_event_value_kib;
+      let input = self.max_event_value_input.get_or_insert(max);
+      let resp = ui
+        .add(egui::DragValue::new(input).clamp_range(0..=64 * 1024))
+        .on_hover_text(
+          "Larger event values are only loaded when the event is opened, 0 = no limit",
+        );
+      // Only tell the backend once the value is committed, not every value dragged past.
+      if (resp.drag_released() || resp.lost_focus()) && *input != max {
+        self.max_event_value_kib = *input;
+        changed = true;
+      }
+    });
+    ui.horizontal(|ui| {
+      ui.label("Large JSON values (nodes): ");
+      changed |= ui
+        .add(egui::DragValue::new(&mut self.large_json_nodes).clamp_range(10..=100_000))
+        .changed();
+    });
+    ui.horizontal(|ui| {
+      ui.label("Expand JSON values (levels): ");
+      changed |= ui
+        .add(egui::DragValue::new(&mut self.json_expand_depth).clamp_range(0..=20))
+        .on_hover_text("Levels of event values that start expanded")
+        .changed();
+    });
+    ui.horizontal(|ui| {
+      ui.label("Frozen table columns: ");
+      changed |= ui
+        .add(egui::DragValue::new(&mut self.frozen_columns).clamp_range(0..=2))
+        .on_hover_text(
+          "Columns kept visible while scrolling wide tables, 0 = fit tables to the window",
+        )
+        .changed();
+    });
+    ui.horizontal_wrapped(|ui| {
+      ui.label("Accent color: ");
+      changed |= ui
+        .selectable_value(&mut self.accent_color, None, "Theme")
+        .changed();
+      for (name, color) in ACCENT_PRESETS {
+        changed |= ui
+          .selectable_value(&mut self.accent_color, Some(*color), *name)
+          .changed();
+      }
+      let mut color = self.accent_color.unwrap_or(ACCENT_PRESETS[0].1);
+      if ui.color_edit_button_srgb(&mut color).changed() {
+        self.accent_color = Some(color);
+        changed = true;
+      }
+    });
+    changed
+  }
+}
@@ -0,0 +1,208 @@
+use egui::*;
+use serde_json::Value;
+
+use super::account::account_anchor;
+use super::format::*;
+use super::SubAppEvent;
+use crate::backend::*;
+
+#[derive(Clone, Copy)]
+enum FieldKind {
+  Identity,
+  Account,
+  Amount,
+  Era,
+  Other,
+}
+
+/// Fields of the staking events with unnamed fields.
+const STAKING_EVENTS: &[(&str, &[(&str, FieldKind)])] = &[
+  (
+    "Staking.Bonded",
+    &[
+      ("Identity", FieldKind::Identity),
+      ("Stash", FieldKind::Account),
+      ("Amount", FieldKind::Amount),
+    ],
+  ),
+  (
+    "Staking.Unbonded",
+    &[
+      ("Identity", FieldKind::Identity),
+      ("Stash", FieldKind::Account),
+      ("Amount", FieldKind::Amount),
+    ],
+  ),
+  (
+    "Staking.Reward",
+    &[
+      ("Identity", FieldKind::Identity),
+      ("Stash", FieldKind::Account),
+      ("Amount", FieldKind::Amount),
+    ],
+  ),
+  (
+    "Staking.Slash",
+    &[
+      ("Validator", FieldKind::Account),
+      ("Amount", FieldKind::Amount),
+    ],
+  ),
+  (
+    "Staking.Withdrawn",
+    &[("Stash", FieldKind::Account), ("Amount", FieldKind::Amount)],
+  ),
+  (
+    "Staking.EraPayout",
+    &[
+      ("Era", FieldKind::Era),
+      ("Validator payout", FieldKind::Amount),
+      ("Remainder", FieldKind::Amount),
+    ],
+  ),
+  (
+    "Staking.PayoutStarted",
+    &[("Era", FieldKind::Era), ("Validator", FieldKind::Account)],
+  ),
+  ("Staking.Chilled", &[("Stash", FieldKind::Account)]),
+  (
+    "Staking.Kicked",
+    &[
+      ("Nominator", FieldKind::Account),
+      ("Stash", FieldKind::Account),
+    ],
+  ),
+  (
+    "Staking.Nominated",
+    &[
+      ("Identity", FieldKind::Identity),
+      ("Nominator", FieldKind::Account),
+      ("Targets", FieldKind::Other),
+    ],
+  ),
+];
+
+/// Guess the kind of a named field, e.g. `Rewarded { stash, amount }`.
+fn named_field_kind(name: &str) -> FieldKind {
+  match name {
+    "stash" | "staker" | "validator" | "nominator" | "who" | "account" => FieldKind::Account,
+    "did" | "identity" => FieldKind::Identity,
+    name if name.contains("era") => FieldKind::Era,
+    name if name.contains("amount") || name.contains("payout") || name == "remainder" => {
+      FieldKind::Amount
+    }
+    _ => FieldKind::Other,
+  }
+}
+
+/// Labeled fields of a staking event, `None` for events without a staking renderer.
+fn staking_fields(event: &EventInfo) -> Option<Vec<(String, FieldKind, &Value)>> {
+  if !event.name.starts_with("Staking.") {
+    return None;
+  }
+  match &event.value {
+    Value::Object(map) => Some(
+      map
+        .iter()
+        .map(|(name, value)| (name.clone(), named_field_kind(name), value))
+        .collect(),
+    ),
+    value => {
+      let (_, fields) = STAKING_EVENTS
+        .iter()
+        .find(|(name, _)| *name == event.name)?;
+      let values = match value {
+        Value::Array(values) => values.iter().collect(),
+        value => vec![value],
+      };
+      if values.len() != fields.len() {
+        return None;
+      }
+      Some(
+        fields
+          .iter()
+          .zip(values)
+          .map(|((name, kind), value)| (name.to_string(), *kind, value))
+          .collect(),
+      )
+    }
+  }
+}
+
+/// Account ids are serialized as SS58/hex strings or byte arrays.
+pub fn value_account(value: &Value) -> Option<AccountKey> {
+  match value {
+    Value::String(account) => parse_account(account),
+    Value::Array(bytes) => bytes
+      .iter()
+      .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+      .collect::<Option<Vec<u8>>>()?
+      .try_into()
+      .ok(),
+    _ => None,
+  }
+}
+
+fn value_amount(value: &Value) -> Option<u128> {
+  match value {
+    Value::Number(amount) => amount.as_u64().map(u128::from),
+    // Large balances can be serialized as strings.
+    Value::String(amount) => amount.parse().ok(),
+    _ => None,
+  }
+}
+
+pub fn raw_value_ui(ui: &mut Ui, value: &Value) {
+  match value {
+    Value::String(value) => ui.monospace(value),
+    value => ui.monospace(value.to_string()),
+  };
+}
+
+/// Staking event fields with accounts linked to the account sub-app, amounts in POLYX and
+/// eras labeled.  Returns `false` for unknown staking events, which should use the generic tree.
+pub fn staking_event_ui(
+  ui: &mut Ui,
+  event: &EventInfo,
+  ss58_prefix: u16,
+  amounts: AmountFormat,
+  app_event: &mut Option<SubAppEvent>,
+) -> bool {
+  let Some(fields) = staking_fields(event) else {
+    return false;
+  };
+  Grid::new(("staking_event", event.block, event.number))
+    .num_columns(2)
+    .striped(true)
+    .show(ui, |ui| {
+      for (name, kind, value) in fields {
+        ui.label(format!("{name}:"));
+        match kind {
+          FieldKind::Account => match value_account(value) {
+            Some(account) => {
+              if ui.link(format_account(&account, ss58_prefix)).clicked() {
+                *app_event = Some(SubAppEvent::OpenApp(account_anchor(&account)));
+              }
+            }
+            None => raw_value_ui(ui, value),
+          },
+          FieldKind::Amount => match value_amount(value) {
+            Some(amount) => {
+              ui.label(format_balance(amount, amounts))
+                .on_hover_text(amount.to_string());
+            }
+            None => raw_value_ui(ui, value),
+          },
+          FieldKind::Era => match value.as_u64() {
+            Some(era) => {
+              ui.label(format!("Era {era}"));
+            }
+            None => raw_value_ui(ui, value),
+          },
+          FieldKind::Identity | FieldKind::Other => raw_value_ui(ui, value),
+        }
+        ui.end_row();
+      }
+    });
+  true
+}
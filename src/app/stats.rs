@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+
+use super::format::format_duration;
+use crate::backend::BlockInfo;
+
+/// Length of the windows the block rate is sampled over, in seconds.
+const RATE_WINDOW: f64 = 1.0;
+/// Weight of the newest sample in the block rate average.
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Smoothed rate of arriving blocks (new and preloaded), an exponential moving average of the
+/// blocks per second in each `RATE_WINDOW`.
+#[derive(Default)]
+pub struct BlockRate {
+  /// Start of the current window, in egui time (seconds).
+  window_start: Option<f64>,
+  window_blocks: u32,
+  rate: Option<f64>,
+}
+
+impl BlockRate {
+  pub fn add_block(&mut self) {
+    self.window_blocks += 1;
+  }
+
+  /// Close the current window once it is `RATE_WINDOW` long.
+  pub fn tick(&mut self, now: f64) {
+    let Some(start) = self.window_start else {
+      self.window_start = Some(now);
+      return;
+    };
+    let elapsed = now - start;
+    if elapsed < RATE_WINDOW {
+      return;
+    }
+    let sample = self.window_blocks as f64 / elapsed;
+    self.rate = Some(match self.rate {
+      Some(rate) => RATE_SMOOTHING * sample + (1.0 - RATE_SMOOTHING) * rate,
+      None => sample,
+    });
+    self.window_start = Some(now);
+    self.window_blocks = 0;
+  }
+
+  /// Blocks per second, `None` until the first window closed.
+  pub fn rate(&self) -> Option<f64> {
+    self.rate
+  }
+}
+
+/// Statistics for the current connection session.
+pub struct SessionStats {
+  pub started: DateTime<Utc>,
+  pub blocks: u64,
+  pub events: u64,
+  pub decode_errors: u64,
+  pub reconnects: u32,
+  pub reorgs: u32,
+  /// Deepest reorg seen, in replaced blocks.
+  pub max_reorg_depth: u32,
+}
+
+impl Default for SessionStats {
+  fn default() -> Self {
+    Self {
+      started: Utc::now(),
+      blocks: 0,
+      events: 0,
+      decode_errors: 0,
+      reconnects: 0,
+      reorgs: 0,
+      max_reorg_depth: 0,
+    }
+  }
+}
+
+impl SessionStats {
+  pub fn add_block(&mut self) {
+    self.blocks += 1;
+  }
+
+  pub fn add_events(&mut self, block: &BlockInfo) {
+    self.events += block.events.len() as u64;
+    self.decode_errors += block.events.iter().filter(|e| e.decode_error).count() as u64;
+  }
+
+  pub fn add_reorg(&mut self, depth: u32) {
+    self.reorgs += 1;
+    self.max_reorg_depth = self.max_reorg_depth.max(depth);
+  }
+
+  pub fn uptime(&self) -> i64 {
+    (Utc::now() - self.started).num_seconds()
+  }
+
+  /// Session panel.  Returns `true` if the user asked to reset the stats.
+  pub fn ui(&self, ui: &mut egui::Ui) -> bool {
+    egui::Grid::new("session_stats_grid")
+      .num_columns(2)
+      .show(ui, |ui| {
+        ui.label("Uptime:");
+        ui.label(format_duration(self.uptime()));
+        ui.end_row();
+        ui.label("Blocks seen:");
+        ui.label(self.blocks.to_string());
+        ui.end_row();
+        ui.label("Events processed:");
+        ui.label(self.events.to_string());
+        ui.end_row();
+        ui.label("Decode errors:");
+        ui.label(self.decode_errors.to_string());
+        ui.end_row();
+        ui.label("Reconnects:");
+        ui.label(self.reconnects.to_string());
+        ui.end_row();
+        ui.label("Reorgs:");
+        ui.label(format!(
+          "{} (deepest: {} blocks)",
+          self.reorgs, self.max_reorg_depth
+        ));
+        ui.end_row();
+      });
+    ui.button("Reset").clicked()
+  }
+}
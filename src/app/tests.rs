@@ -0,0 +1,488 @@
+use std::time::Duration;
+
+use codec::Decode;
+use serde_json::Value;
+
+use super::*;
+
+/// Frontend state driven by a mock backend, and the mock for scripting it.
+fn mock_state() -> (BackendState, MockBackend) {
+  let mock = MockBackend::new();
+  let state = BackendState::with_backend(mock.clone());
+  (state, mock)
+}
+
+/// Header of a test block, `fork` tells apart blocks with the same number and parent.
+fn test_header(number: BlockNumber, parent: BlockHash, fork: u8) -> Header {
+  // Decoded like the node's headers: the fork is the state root, and there are no digest logs.
+  let mut data = parent.encode();
+  Compact(number).encode_to(&mut data);
+  data.extend([fork; 32]);
+  data.extend([0; 33]);
+  Header::decode(&mut &data[..]).expect("valid header")
+}
+
+/// Block with `events` events after `parent`, the genesis block without a parent.
+fn test_block(parent: Option<&BlockInfo>, fork: u8, events: u32) -> BlockInfo {
+  let (number, parent) = parent.map_or((0, BlockHash::default()), |parent| {
+    (parent.number() + 1, parent.hash)
+  });
+  let header = test_header(number, parent, fork);
+  BlockInfo {
+    hash: header.hash(),
+    header,
+    timestamp: None,
+    events: (0..events)
+      .map(|index| EventInfo {
+        block: number,
+        number: index,
+        phase: Phase::ApplyExtrinsic(0),
+        name: "Balances.Transfer".into(),
+        value: Value::Null,
+        decode_error: false,
+        elided: None,
+      })
+      .collect(),
+    events_error: None,
+    from_archive: false,
+    extrinsics: Vec::new(),
+    header_only: false,
+    spec_version: None,
+    summary: None,
+  }
+}
+
+/// Blocks `0..len` of a chain, with 3 events each.
+fn test_chain(len: u32) -> Vec<BlockInfo> {
+  let mut blocks = Vec::new();
+  for _ in 0..len {
+    let block = test_block(blocks.last(), 0, 3);
+    blocks.push(block);
+  }
+  blocks
+}
+
+/// Feed a historical block, e.g. a preloaded one.
+fn feed(state: &mut BackendState, mock: &MockBackend, block: BlockInfo) {
+  mock.push_event(BackendEvent::BlockInfo(block));
+  state.backend_updates();
+}
+
+/// Feed a block the way a new chain head arrives.
+fn feed_tip(state: &mut BackendState, mock: &MockBackend, block: BlockInfo) {
+  state.pending_tips.insert(block.hash);
+  feed(state, mock, block);
+}
+
+/// Announce a new chain head, and answer the frontend's request for it.
+fn announce(state: &mut BackendState, mock: &MockBackend, block: &BlockInfo) {
+  mock.push_event(BackendEvent::NewHeader(block.header.clone()));
+  state.backend_updates();
+  let requested = mock
+    .take_requests()
+    .into_iter()
+    .any(|req| matches!(req, BackendRequest::GetBlockInfo(hash) if hash == block.hash));
+  if requested {
+    feed(state, mock, block.clone());
+  }
+}
+
+/// Connect to a node of the chain with `genesis`.
+fn connect(state: &mut BackendState, mock: &MockBackend, genesis: BlockHash, is_reconnect: bool) {
+  mock.push_event(BackendEvent::Connected {
+    genesis,
+    is_reconnect,
+  });
+  state.backend_updates();
+}
+
+#[test]
+fn block_number_anchor_requests_the_block() {
+  let (mut state, mock) = mock_state();
+  state.best_block = 100;
+  let mut app = BlockDetailsApp::default();
+  let res = app.parse_anchor_and_load_block(&state, "block_details/42");
+  assert!(matches!(res, Ok(None)));
+  let requests = mock.take_requests();
+  assert!(requests
+    .iter()
+    .any(|req| matches!(req, BackendRequest::GetBlockByNumber(42))));
+
+  // The node has no block #42.
+  mock.push_event(BackendEvent::BlockNotFound(42));
+  state.backend_updates();
+  let res = app.parse_anchor_and_load_block(&state, "block_details/42");
+  assert_eq!(res.err().as_deref(), Some("Block #42 not found"));
+  // Only requested once.
+  assert!(mock.take_requests().is_empty());
+}
+
+#[test]
+fn unproduced_block_number_waits_for_the_block() {
+  let (mut state, mock) = mock_state();
+  state.best_block = 10;
+  let mut app = BlockDetailsApp::default();
+  let _ = app.parse_anchor_and_load_block(&state, "block_details/42");
+  mock.push_event(BackendEvent::BlockNotFound(42));
+  state.backend_updates();
+  let res = app.parse_anchor_and_load_block(&state, "block_details/42");
+  assert!(matches!(res, Ok(None)));
+  assert_eq!(app.view.pending_number, Some(42));
+}
+
+#[test]
+fn draining_stops_at_the_budget() {
+  let (mut state, mock) = mock_state();
+  for time in 0..10 {
+    mock.push_event(BackendEvent::BlockAtTime { time, hash: None });
+  }
+  // A used up budget still applies one update per frame.
+  let used_up = UpdateBudget::start(Duration::ZERO);
+  assert_eq!(state.backend_updates_within(used_up), 1);
+  assert_eq!(state.lagging_frames, 1);
+  assert_eq!(state.blocks_at_time.len(), 1);
+
+  // The rest are applied once there is time.
+  let budget = UpdateBudget::start(Duration::from_secs(60));
+  assert_eq!(state.backend_updates_within(budget), 9);
+  assert_eq!(state.lagging_frames, 0);
+  assert_eq!(state.blocks_at_time.len(), 10);
+}
+
+#[test]
+fn summarized_blocks_keep_their_counts() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(3);
+  announce(&mut state, &mock, &chain[2]);
+  // Historical blocks arriving while catching up are summarized.
+  state.lagging_frames = LAGGING_FRAMES;
+  feed(&mut state, &mock, chain[1].clone());
+  assert_eq!(state.summarized_blocks, 1);
+  let block = &state.blocks[&1];
+  assert!(block.header_only && block.events.is_empty());
+  assert_eq!(block.event_count(), 3);
+  assert_eq!(block.pallet_event_counts().get("Balances"), Some(&3));
+}
+
+#[test]
+fn default_hash_anchor_doesnt_request_a_block() {
+  let (state, mock) = mock_state();
+  let mut app = BlockDetailsApp::default();
+  let anchor = format!("block_details/0x{}", "00".repeat(32));
+  let res = app.parse_anchor_and_load_block(&state, &anchor);
+  assert!(matches!(res, Ok(None)));
+  assert!(matches!(app.selected_block, SelectedBlock::None));
+  // Nodes don't have a block for the zero hash.
+  assert!(mock.take_requests().is_empty());
+}
+
+#[test]
+fn same_block_fed_twice_is_counted_once() {
+  let (mut state, mock) = mock_state();
+  let block = test_chain(11).pop().unwrap();
+  // Preloaded, and then announced as the new head.
+  feed(&mut state, &mock, block.clone());
+  feed_tip(&mut state, &mock, block);
+  assert_eq!(state.stats.blocks, 1);
+  assert_eq!(state.stats.events, 3);
+  assert_eq!(state.recent_blocks.len(), 1);
+  // The block's transfers are one entry of the feed.
+  assert_eq!(state.recent_events.len(), 1);
+  assert_eq!(state.recent_events[0].count, 3);
+}
+
+#[test]
+fn header_only_copy_keeps_the_full_block() {
+  let (mut state, mock) = mock_state();
+  let block = test_chain(11).pop().unwrap();
+  let mut header_only = block.clone();
+  header_only.events.clear();
+  header_only.header_only = true;
+  // A lazy preload before and after the full block.
+  feed(&mut state, &mock, header_only.clone());
+  feed(&mut state, &mock, block);
+  feed(&mut state, &mock, header_only);
+  assert_eq!(state.stats.blocks, 1);
+  assert_eq!(state.stats.events, 3);
+  assert!(!state.blocks[&10].header_only);
+  assert_eq!(state.blocks[&10].events.len(), 3);
+}
+
+#[test]
+fn reloaded_block_indexes_its_calls() {
+  let (mut state, mock) = mock_state();
+  let mut block = test_block(None, 0, 3);
+  // Loaded while decoding was disabled.
+  feed(&mut state, &mock, block.clone());
+  assert!(state.call_index.calls.is_empty());
+  block.extrinsics.push(ExtrinsicInfo {
+    index: 0,
+    signer: None,
+    era: None,
+    tip: None,
+    nonce: None,
+    call: Some(CallInfo {
+      name: "Balances.transfer".into(),
+      args: Value::Null,
+      calls: Vec::new(),
+    }),
+    raw: None,
+  });
+  feed(&mut state, &mock, block.clone());
+  feed(&mut state, &mock, block);
+  assert_eq!(state.call_index.calls["Balances.transfer"].count, 1);
+  assert_eq!(state.stats.events, 3);
+}
+
+#[test]
+fn caches_arent_saved() {
+  let (mut state, mock) = mock_state();
+  let empty = serde_json::to_vec(&state).unwrap().len();
+  for mut block in test_chain(500) {
+    block.extrinsics.push(ExtrinsicInfo {
+      index: 0,
+      signer: None,
+      era: None,
+      tip: None,
+      nonce: None,
+      call: Some(CallInfo {
+        name: "Balances.transfer".into(),
+        args: Value::Null,
+        calls: Vec::new(),
+      }),
+      raw: None,
+    });
+    feed_tip(&mut state, &mock, block);
+  }
+  assert_eq!(state.blocks.len(), 500);
+  assert!(!state.recent_events.is_empty());
+  assert_eq!(state.call_index.calls["Balances.transfer"].count, 500);
+  // Only settings, pins and bookmarks are saved, the caches aren't.
+  let saved = serde_json::to_vec(&state).unwrap().len();
+  assert!(
+    saved < empty + 256,
+    "saved state grew from {empty} to {saved} bytes"
+  );
+}
+
+#[test]
+fn same_chain_reconnect_doesnt_reload_blocks() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(5);
+  let genesis = chain[0].hash;
+  connect(&mut state, &mock, genesis, false);
+  for block in &chain {
+    announce(&mut state, &mock, block);
+  }
+  assert_eq!(state.blocks.len(), 5);
+  mock.take_requests();
+
+  // Another node of the same chain announces the cached head.
+  connect(&mut state, &mock, genesis, true);
+  mock.push_event(BackendEvent::NewHeader(chain[4].header.clone()));
+  state.backend_updates();
+  let requests = mock.take_requests();
+  assert!(
+    !requests.iter().any(|req| matches!(
+      req,
+      BackendRequest::GetBlockInfo(_)
+        | BackendRequest::PreloadBlock(_)
+        | BackendRequest::GetBlockByNumber(_)
+        | BackendRequest::GetBlockRange { .. }
+    )),
+    "redundant block requests: {requests:?}"
+  );
+  assert_eq!(state.stats.reconnects, 1);
+  assert_eq!(state.blocks.len(), 5);
+  assert_eq!(state.best_block, 4);
+}
+
+#[test]
+fn cached_block_announced_as_head_becomes_best() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(5);
+  for block in &chain[..3] {
+    announce(&mut state, &mock, block);
+  }
+  // The next blocks arrive before they are announced, e.g. preloaded or by number.
+  feed(&mut state, &mock, chain[3].clone());
+  feed(&mut state, &mock, chain[4].clone());
+  assert_eq!(state.best_block, 2);
+  mock.take_requests();
+
+  mock.push_event(BackendEvent::NewHeader(chain[4].header.clone()));
+  state.backend_updates();
+  let requests = mock.take_requests();
+  assert!(
+    !requests
+      .iter()
+      .any(|req| matches!(req, BackendRequest::GetBlockInfo(_))),
+    "cached head requested: {requests:?}"
+  );
+  assert_eq!(state.best_block, 4);
+  assert_eq!(state.best_hash, Some(chain[4].hash));
+  assert_eq!(state.recent_blocks().next(), Some(4));
+  assert_eq!(state.stats.reorgs, 0);
+}
+
+#[test]
+fn historical_block_fed_first_isnt_best() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(10);
+  // E.g. from a block range, before the first chain head arrived.
+  feed(&mut state, &mock, chain[8].clone());
+  assert_eq!(state.best_block, 0);
+  assert_eq!(state.best_hash, None);
+
+  feed_tip(&mut state, &mock, chain[5].clone());
+  assert_eq!(state.best_block, 5);
+  assert_eq!(state.best_hash, Some(chain[5].hash));
+  assert_eq!(state.recent_blocks.front(), Some(&5));
+
+  // Newer historical blocks don't replace the best block either.
+  feed(&mut state, &mock, chain[9].clone());
+  assert_eq!(state.best_block, 5);
+  assert_eq!(state.recent_blocks.front(), Some(&5));
+}
+
+#[test]
+fn fork_replacing_the_best_block_is_a_reorg() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(6);
+  for block in &chain {
+    announce(&mut state, &mock, block);
+  }
+  assert_eq!(state.best_block, 5);
+  // A fork from #3 overtakes the chain.
+  let mut parent = chain[3].clone();
+  for _ in 4..=6 {
+    let block = test_block(Some(&parent), 1, 3);
+    announce(&mut state, &mock, &block);
+    parent = block;
+  }
+  assert_eq!(state.best_block, 6);
+  assert_eq!(state.best_hash, Some(parent.hash));
+  assert_eq!(state.stats.reorgs, 1);
+  // #4 and #5 were replaced.
+  assert_eq!(state.stats.max_reorg_depth, 2);
+  assert!(state.block_by_hash(&chain[4].hash).is_none());
+  assert!(state.block_by_hash(&parent.hash).is_some());
+}
+
+#[test]
+fn replaced_block_anchor_requests_the_block() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(3);
+  for block in &chain {
+    announce(&mut state, &mock, block);
+  }
+  // A fork replaces #2.
+  let fork = test_block(Some(&chain[1]), 1, 3);
+  announce(&mut state, &mock, &fork);
+  announce(&mut state, &mock, &test_block(Some(&fork), 1, 3));
+  assert_eq!(state.stats.reorgs, 1);
+
+  let replaced = chain[2].hash;
+  let mut app = BlockDetailsApp::default();
+  let anchor = format!("block_details/{replaced:?}");
+  let res = app.parse_anchor_and_load_block(&state, &anchor);
+  // Not the fork's block at the same height.
+  assert!(matches!(res, Ok(None)));
+  let requests = mock.take_requests();
+  assert!(requests
+    .iter()
+    .any(|req| matches!(req, BackendRequest::GetBlockInfo(hash) if *hash == replaced)));
+}
+
+#[test]
+fn tip_descending_from_the_best_block_isnt_a_reorg() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(5);
+  for block in &chain[..3] {
+    announce(&mut state, &mock, block);
+  }
+  // #3 arrives as history, then #4 as the next tip.
+  feed(&mut state, &mock, chain[3].clone());
+  announce(&mut state, &mock, &chain[4]);
+  assert_eq!(state.best_block, 4);
+  assert_eq!(state.stats.reorgs, 0);
+  assert!(state.error_log.is_empty(), "errors: {:?}", state.error_log);
+}
+
+#[test]
+fn trim_drops_the_oldest_unused_blocks() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(MAX_RECENT_BLOCKS as u32 + 10);
+  // Preloaded newest first.
+  for block in chain.iter().rev() {
+    mock.push_event(BackendEvent::BlockInfo(block.clone()));
+  }
+  while state.backend_updates() > 0 {}
+  // A view shows the first blocks, and one is pinned.
+  state.keep_blocks(0..5);
+  state.pinned_blocks.insert(chain[5].hash);
+  state.trim_blocks();
+
+  assert_eq!(state.recent_blocks.len(), MAX_RECENT_BLOCKS);
+  for number in (0..6).chain(16..chain.len() as u32) {
+    assert!(state.blocks.contains_key(&number), "#{number} trimmed");
+  }
+  for block in &chain[6..16] {
+    assert!(!state.blocks.contains_key(&block.number()));
+    assert!(!state.hash_to_number.contains_key(&block.hash));
+    assert!(!state.recent_blocks.contains(&block.number()));
+  }
+}
+
+#[test]
+fn preload_walks_back_from_the_tip() {
+  let (mut state, mock) = mock_state();
+  let chain = test_chain(11);
+  let preloads = |mock: &MockBackend| {
+    mock
+      .take_requests()
+      .into_iter()
+      .filter_map(|req| match req {
+        BackendRequest::PreloadBlock(hash) => Some(hash),
+        _ => None,
+      })
+      .collect::<Vec<_>>()
+  };
+  announce(&mut state, &mock, &chain[10]);
+  assert_eq!(preloads(&mock), [chain[9].hash]);
+  feed(&mut state, &mock, chain[9].clone());
+  assert_eq!(preloads(&mock), [chain[8].hash]);
+
+  // Paused preloading continues from the same block.
+  state.set_preload_paused(true);
+  feed(&mut state, &mock, chain[8].clone());
+  assert!(preloads(&mock).is_empty());
+  state.set_preload_paused(false);
+  assert_eq!(preloads(&mock), [chain[7].hash]);
+
+  // Other historical blocks don't move the preloading.
+  feed(&mut state, &mock, chain[2].clone());
+  assert!(preloads(&mock).is_empty());
+}
+
+#[test]
+fn chain_status_is_requested_once_per_interval() {
+  let (mut state, mock) = mock_state();
+  let chain_status_requests = |mock: &MockBackend| {
+    mock
+      .take_requests()
+      .iter()
+      .filter(|req| matches!(req, BackendRequest::GetChainStatus))
+      .count()
+  };
+  // Not connected yet.
+  state.refresh_chain_status(0.0);
+  assert_eq!(chain_status_requests(&mock), 0);
+
+  connect(&mut state, &mock, test_chain(1)[0].hash, false);
+  state.refresh_chain_status(1.0);
+  state.refresh_chain_status(2.0);
+  assert_eq!(chain_status_requests(&mock), 1);
+  state.refresh_chain_status(1.0 + CHAIN_STATUS_INTERVAL);
+  assert_eq!(chain_status_requests(&mock), 1);
+}
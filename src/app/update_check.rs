@@ -0,0 +1,110 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Latest release of the explorer on GitHub.
+const LATEST_RELEASE_URL: &str =
+  "https://api.github.com/repos/PolymeshAssociation/polymesh_api_example_gui/releases/latest";
+/// Check for updates at most once a day.
+const CHECK_INTERVAL_MS: u64 = 24 * 60 * 60 * 1000;
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Release {
+  /// Version without the `v` prefix, e.g. `0.2.0`.
+  pub version: String,
+  /// Release page.
+  pub url: String,
+}
+
+/// Checks the GitHub releases for a newer version.  The result is saved with the app state, so
+/// it isn't checked on every launch.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct UpdateCheck {
+  /// Time of the last check, in milliseconds since the unix epoch.
+  checked_at: u64,
+  latest: Option<Release>,
+  #[serde(skip)]
+  pending: Option<mpsc::Receiver<Result<Release, String>>>,
+}
+
+impl UpdateCheck {
+  /// Start a check when the cached result is stale, and collect the result of a running check.
+  /// Returns `true` if the cache changed.
+  pub fn update(&mut self) -> bool {
+    if let Some(pending) = &self.pending {
+      return match pending.try_recv() {
+        Ok(res) => {
+          self.pending = None;
+          match res {
+            Ok(release) => self.latest = Some(release),
+            Err(err) => log::warn!("Update check failed: {err}"),
+          }
+          true
+        }
+        Err(mpsc::TryRecvError::Empty) => false,
+        Err(mpsc::TryRecvError::Disconnected) => {
+          self.pending = None;
+          false
+        }
+      };
+    }
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    if now.saturating_sub(self.checked_at) < CHECK_INTERVAL_MS {
+      return false;
+    }
+    // Failed checks also wait for the next interval.
+    self.checked_at = now;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+      let _ = tx.send(latest_release());
+    });
+    self.pending = Some(rx);
+    true
+  }
+
+  /// The latest release, if it is newer than this build.
+  pub fn available(&self) -> Option<&Release> {
+    self
+      .latest
+      .as_ref()
+      .filter(|release| is_newer(&release.version, env!("CARGO_PKG_VERSION")))
+  }
+}
+
+fn latest_release() -> Result<Release, String> {
+  let release: serde_json::Value = ureq::get(LATEST_RELEASE_URL)
+    // Required by the GitHub API.
+    .set("User-Agent", env!("CARGO_PKG_NAME"))
+    .timeout(CHECK_TIMEOUT)
+    .call()
+    .map_err(|e| e.to_string())?
+    .into_json()
+    .map_err(|e| e.to_string())?;
+  let version = release["tag_name"]
+    .as_str()
+    .ok_or("Release missing tag_name")?;
+  let url = release["html_url"]
+    .as_str()
+    .ok_or("Release missing html_url")?;
+  Ok(Release {
+    version: version.trim_start_matches('v').to_string(),
+    url: url.to_string(),
+  })
+}
+
+/// Numeric parts of a `major.minor.patch` version, ignoring pre-release and build suffixes.
+fn version_parts(version: &str) -> Vec<u64> {
+  version
+    .trim_start_matches('v')
+    .split(['-', '+'])
+    .next()
+    .unwrap_or_default()
+    .split('.')
+    .map(|part| part.parse().unwrap_or(0))
+    .collect()
+}
+
+fn is_newer(latest: &str, current: &str) -> bool {
+  version_parts(latest) > version_parts(current)
+}
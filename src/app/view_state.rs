@@ -0,0 +1,40 @@
+use codec::{Decode, Encode};
+
+use crate::backend::*;
+
+/// Prefix of encoded view states, so they can be told apart from other pasted identifiers.
+const VIEW_PREFIX: &str = "view:";
+
+/// Shareable navigation state: the open view, the events feed filters and the pins.
+#[derive(Clone, Debug, Default, Encode, Decode)]
+pub struct ViewState {
+  pub anchor: String,
+  /// Pallets shown in the events feed.
+  pub pallets: Vec<String>,
+  pub group_events: bool,
+  pub range_start: Option<BlockNumber>,
+  pub pinned_blocks: Vec<BlockHash>,
+  pub pinned_events: Vec<(BlockHash, u32)>,
+}
+
+impl ViewState {
+  /// SCALE encoded and base58, for the `view` url parameter.
+  pub fn to_base58(&self) -> String {
+    bs58::encode(self.encode()).into_string()
+  }
+
+  pub fn from_base58(data: &str) -> Option<Self> {
+    let data = bs58::decode(data).into_vec().ok()?;
+    Self::decode(&mut data.as_slice()).ok()
+  }
+
+  /// Compact string for sharing, e.g. `view:3yZe7d...`.
+  pub fn to_code(&self) -> String {
+    format!("{VIEW_PREFIX}{}", self.to_base58())
+  }
+
+  /// Parse a string from `to_code`, `None` if it isn't a view state.
+  pub fn from_code(code: &str) -> Option<Self> {
+    Self::from_base58(code.trim().strip_prefix(VIEW_PREFIX)?)
+  }
+}
@@ -0,0 +1,545 @@
+//! Minimal decoder of the runtime metadata (V14), for reading the pallet constants.
+
+use std::collections::HashMap;
+
+use codec::{Compact, Decode};
+use serde_json::{Map, Value};
+
+use super::Result;
+
+/// `meta` magic number at the start of the encoded metadata.
+const META_MAGIC: u32 = 0x6174_656d;
+const META_VERSION: u8 = 14;
+/// Deepest nesting of types decoded, guards against recursive types.
+const MAX_TYPE_DEPTH: usize = 64;
+
+/// A pallet constant with its decoded value.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ConstantInfo {
+  pub name: String,
+  /// Type name, e.g. `u128` or `Perbill`.
+  pub type_name: String,
+  /// Decoded value, the raw value as hex if it couldn't be decoded.
+  pub value: Value,
+  pub docs: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PalletConstants {
+  pub pallet: String,
+  pub constants: Vec<ConstantInfo>,
+}
+
+// Metadata types, the unused fields are decoded to skip over them.
+
+#[allow(dead_code)]
+#[derive(Decode)]
+struct Field {
+  name: Option<String>,
+  ty: Compact<u32>,
+  type_name: Option<String>,
+  docs: Vec<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+struct Variant {
+  name: String,
+  fields: Vec<Field>,
+  index: u8,
+  docs: Vec<String>,
+}
+
+#[derive(Clone, Copy, Decode)]
+enum Primitive {
+  Bool,
+  Char,
+  Str,
+  U8,
+  U16,
+  U32,
+  U64,
+  U128,
+  U256,
+  I8,
+  I16,
+  I32,
+  I64,
+  I128,
+  I256,
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+enum TypeDef {
+  Composite(Vec<Field>),
+  Variant(Vec<Variant>),
+  Sequence(Compact<u32>),
+  Array(u32, Compact<u32>),
+  Tuple(Vec<Compact<u32>>),
+  Primitive(Primitive),
+  Compact(Compact<u32>),
+  BitSequence(Compact<u32>, Compact<u32>),
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+struct TypeParam {
+  name: String,
+  ty: Option<Compact<u32>>,
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+struct Type {
+  path: Vec<String>,
+  params: Vec<TypeParam>,
+  def: TypeDef,
+  docs: Vec<String>,
+}
+
+#[derive(Decode)]
+struct PortableType {
+  id: Compact<u32>,
+  ty: Type,
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+enum StorageEntryType {
+  Plain(Compact<u32>),
+  Map {
+    /// One byte per hasher.
+    hashers: Vec<u8>,
+    key: Compact<u32>,
+    value: Compact<u32>,
+  },
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+struct StorageEntry {
+  name: String,
+  modifier: u8,
+  ty: StorageEntryType,
+  default: Vec<u8>,
+  docs: Vec<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+struct PalletStorage {
+  prefix: String,
+  entries: Vec<StorageEntry>,
+}
+
+#[derive(Decode)]
+struct PalletConstant {
+  name: String,
+  ty: Compact<u32>,
+  value: Vec<u8>,
+  docs: Vec<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Decode)]
+struct Pallet {
+  name: String,
+  storage: Option<PalletStorage>,
+  calls: Option<Compact<u32>>,
+  event: Option<Compact<u32>>,
+  constants: Vec<PalletConstant>,
+  error: Option<Compact<u32>>,
+  index: u8,
+}
+
+/// Decode the constants of all pallets from SCALE encoded metadata, from `state_getMetadata`.
+pub fn decode_constants(metadata: &[u8]) -> Result<Vec<PalletConstants>> {
+  let mut input = metadata;
+  let magic = u32::decode(&mut input).map_err(|e| e.to_string())?;
+  if magic != META_MAGIC {
+    return Err("Not runtime metadata".into());
+  }
+  let version = u8::decode(&mut input).map_err(|e| e.to_string())?;
+  if version != META_VERSION {
+    return Err(format!("Unsupported metadata version: {version}"));
+  }
+  let types = Vec::<PortableType>::decode(&mut input)
+    .map_err(|e| format!("Failed to decode metadata types: {e}"))?;
+  let registry = Registry {
+    types: types.into_iter().map(|ty| (ty.id.0, ty.ty)).collect(),
+  };
+  let pallets = Vec::<Pallet>::decode(&mut input)
+    .map_err(|e| format!("Failed to decode metadata pallets: {e}"))?;
+  Ok(
+    pallets
+      .into_iter()
+      .filter(|pallet| !pallet.constants.is_empty())
+      .map(|pallet| PalletConstants {
+        pallet: pallet.name,
+        constants: pallet
+          .constants
+          .into_iter()
+          .map(|constant| registry.constant(constant))
+          .collect(),
+      })
+      .collect(),
+  )
+}
+
+fn hex_value(bytes: &[u8]) -> Value {
+  Value::String(format!("0x{}", hex::encode(bytes)))
+}
+
+/// Numbers that don't fit JSON numbers are strings.
+fn u128_value(n: u128) -> Value {
+  u64::try_from(n).map_or_else(|_| Value::String(n.to_string()), Value::from)
+}
+
+fn i128_value(n: i128) -> Value {
+  i64::try_from(n).map_or_else(|_| Value::String(n.to_string()), Value::from)
+}
+
+struct Registry {
+  types: HashMap<u32, Type>,
+}
+
+impl Registry {
+  fn constant(&self, constant: PalletConstant) -> ConstantInfo {
+    let ty = constant.ty.0;
+    let mut input = constant.value.as_slice();
+    let value = match self.decode_value(ty, &mut input, 0) {
+      Ok(value) if input.is_empty() => value,
+      Ok(_) | Err(_) => hex_value(&constant.value),
+    };
+    ConstantInfo {
+      name: constant.name,
+      type_name: self.type_name(ty, 0),
+      value,
+      docs: constant.docs.join("\n").trim().to_string(),
+    }
+  }
+
+  fn get(&self, id: u32) -> Result<&Type> {
+    self
+      .types
+      .get(&id)
+      .ok_or_else(|| format!("Unknown type id: {id}"))
+  }
+
+  fn type_name(&self, id: u32, depth: usize) -> String {
+    let Ok(ty) = self.get(id) else {
+      return format!("<{id}>");
+    };
+    if depth > MAX_TYPE_DEPTH {
+      return "..".into();
+    }
+    if let Some(name) = ty.path.last() {
+      return name.clone();
+    }
+    match &ty.def {
+      TypeDef::Sequence(inner) => format!("Vec<{}>", self.type_name(inner.0, depth + 1)),
+      TypeDef::Array(len, inner) => format!("[{}; {len}]", self.type_name(inner.0, depth + 1)),
+      TypeDef::Tuple(fields) => {
+        let names: Vec<_> = fields
+          .iter()
+          .map(|field| self.type_name(field.0, depth + 1))
+          .collect();
+        format!("({})", names.join(", "))
+      }
+      TypeDef::Compact(inner) => format!("Compact<{}>", self.type_name(inner.0, depth + 1)),
+      TypeDef::Primitive(primitive) => primitive_name(*primitive).into(),
+      TypeDef::BitSequence(..) => "BitVec".into(),
+      TypeDef::Composite(_) | TypeDef::Variant(_) => format!("<{id}>"),
+    }
+  }
+
+  fn decode_value(&self, id: u32, input: &mut &[u8], depth: usize) -> Result<Value> {
+    if depth > MAX_TYPE_DEPTH {
+      return Err("Type nested too deep".into());
+    }
+    let depth = depth + 1;
+    let ty = self.get(id)?;
+    Ok(match &ty.def {
+      TypeDef::Composite(fields) => self.decode_fields(fields, input, depth)?,
+      TypeDef::Variant(variants) => {
+        let index = u8::decode(input).map_err(|e| e.to_string())?;
+        let variant = variants
+          .iter()
+          .find(|variant| variant.index == index)
+          .ok_or_else(|| format!("Unknown variant {index} of type {id}"))?;
+        let value = self.decode_fields(&variant.fields, input, depth)?;
+        let is_option = ty.path.last().is_some_and(|name| name == "Option");
+        match (is_option, value) {
+          (true, value) => value,
+          (false, Value::Null) => Value::String(variant.name.clone()),
+          (false, value) => {
+            let mut map = Map::new();
+            map.insert(variant.name.clone(), value);
+            Value::Object(map)
+          }
+        }
+      }
+      TypeDef::Sequence(inner) => {
+        let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0;
+        self.decode_items(inner.0, len, input, depth)?
+      }
+      TypeDef::Array(len, inner) => self.decode_items(inner.0, *len, input, depth)?,
+      TypeDef::Tuple(fields) => Value::Array(
+        fields
+          .iter()
+          .map(|field| self.decode_value(field.0, input, depth))
+          .collect::<Result<_>>()?,
+      ),
+      TypeDef::Primitive(primitive) => decode_primitive(*primitive, input)?,
+      TypeDef::Compact(_) => {
+        // Compact encoding is the same for every unsigned integer size.
+        u128_value(Compact::<u128>::decode(input).map_err(|e| e.to_string())?.0)
+      }
+      TypeDef::BitSequence(..) => return Err("Bit sequences aren't supported".into()),
+    })
+  }
+
+  /// Named fields as an object, unnamed fields as an array and a single unnamed field as its
+  /// value.  No fields is `null`.
+  fn decode_fields(&self, fields: &[Field], input: &mut &[u8], depth: usize) -> Result<Value> {
+    match fields {
+      [] => Ok(Value::Null),
+      [field] if field.name.is_none() => self.decode_value(field.ty.0, input, depth),
+      fields if fields.iter().all(|field| field.name.is_some()) => {
+        let mut map = Map::new();
+        for field in fields {
+          let value = self.decode_value(field.ty.0, input, depth)?;
+          map.insert(field.name.clone().unwrap_or_default(), value);
+        }
+        Ok(Value::Object(map))
+      }
+      fields => Ok(Value::Array(
+        fields
+          .iter()
+          .map(|field| self.decode_value(field.ty.0, input, depth))
+          .collect::<Result<_>>()?,
+      )),
+    }
+  }
+
+  /// Byte sequences are hex, other sequences arrays.
+  fn decode_items(&self, id: u32, len: u32, input: &mut &[u8], depth: usize) -> Result<Value> {
+    let is_bytes = matches!(self.get(id)?.def, TypeDef::Primitive(Primitive::U8));
+    if is_bytes {
+      let len = len as usize;
+      if input.len() < len {
+        return Err("Not enough data for bytes".into());
+      }
+      let (bytes, rest) = input.split_at(len);
+      *input = rest;
+      return Ok(hex_value(bytes));
+    }
+    // Every item takes at least a byte, don't trust the length otherwise.
+    if len as usize > input.len() {
+      return Err("Not enough data for sequence".into());
+    }
+    Ok(Value::Array(
+      (0..len)
+        .map(|_| self.decode_value(id, input, depth))
+        .collect::<Result<_>>()?,
+    ))
+  }
+}
+
+fn primitive_name(primitive: Primitive) -> &'static str {
+  match primitive {
+    Primitive::Bool => "bool",
+    Primitive::Char => "char",
+    Primitive::Str => "str",
+    Primitive::U8 => "u8",
+    Primitive::U16 => "u16",
+    Primitive::U32 => "u32",
+    Primitive::U64 => "u64",
+    Primitive::U128 => "u128",
+    Primitive::U256 => "u256",
+    Primitive::I8 => "i8",
+    Primitive::I16 => "i16",
+    Primitive::I32 => "i32",
+    Primitive::I64 => "i64",
+    Primitive::I128 => "i128",
+    Primitive::I256 => "i256",
+  }
+}
+
+fn decode_primitive(primitive: Primitive, input: &mut &[u8]) -> Result<Value> {
+  fn decode<T: Decode>(input: &mut &[u8]) -> Result<T> {
+    T::decode(input).map_err(|e| e.to_string())
+  }
+  Ok(match primitive {
+    Primitive::Bool => Value::Bool(decode(input)?),
+    Primitive::Char => {
+      let c = char::from_u32(decode(input)?).ok_or("Invalid char")?;
+      Value::String(c.to_string())
+    }
+    Primitive::Str => Value::String(decode(input)?),
+    Primitive::U8 => Value::from(decode::<u8>(input)?),
+    Primitive::U16 => Value::from(decode::<u16>(input)?),
+    Primitive::U32 => Value::from(decode::<u32>(input)?),
+    Primitive::U64 => Value::from(decode::<u64>(input)?),
+    Primitive::U128 => u128_value(decode(input)?),
+    Primitive::I8 => Value::from(decode::<i8>(input)?),
+    Primitive::I16 => Value::from(decode::<i16>(input)?),
+    Primitive::I32 => Value::from(decode::<i32>(input)?),
+    Primitive::I64 => Value::from(decode::<i64>(input)?),
+    Primitive::I128 => i128_value(decode(input)?),
+    Primitive::U256 | Primitive::I256 => hex_value(&decode::<[u8; 32]>(input)?),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use codec::Encode;
+  use frame_metadata::v14::{
+    ExtrinsicMetadata, PalletCallMetadata, PalletConstantMetadata, PalletEventMetadata,
+    PalletMetadata, PalletStorageMetadata, RuntimeMetadataV14, StorageEntryMetadata,
+    StorageEntryModifier, StorageEntryType, StorageHasher,
+  };
+  use frame_metadata::RuntimeMetadataPrefixed;
+  use scale_info::{meta_type, TypeInfo};
+  use serde_json::json;
+
+  use super::decode_constants;
+
+  #[derive(Encode, TypeInfo)]
+  struct Limits {
+    max: u32,
+    name: Vec<u8>,
+  }
+
+  #[derive(Encode, TypeInfo)]
+  enum Mode {
+    Off,
+    On(u8),
+  }
+
+  #[derive(TypeInfo)]
+  enum Call {
+    #[allow(dead_code)]
+    Transfer { amount: u128 },
+  }
+
+  fn constant<T: Encode + TypeInfo + 'static>(
+    name: &'static str,
+    value: T,
+  ) -> PalletConstantMetadata {
+    PalletConstantMetadata {
+      name,
+      ty: meta_type::<T>(),
+      value: value.encode(),
+      docs: vec![" First line.", " Second line."],
+    }
+  }
+
+  /// Metadata with a pallet of each kind of type, built by the crates the node uses.
+  fn test_metadata() -> Vec<u8> {
+    let storage = PalletStorageMetadata {
+      prefix: "Test",
+      entries: vec![StorageEntryMetadata {
+        name: "Values",
+        modifier: StorageEntryModifier::Default,
+        ty: StorageEntryType::Map {
+          hashers: vec![StorageHasher::Blake2_128Concat],
+          key: meta_type::<u32>(),
+          value: meta_type::<u64>(),
+        },
+        default: vec![0; 8],
+        docs: vec!["Values"],
+      }],
+    };
+    let pallets = vec![
+      // Pallets without constants are left out.
+      PalletMetadata {
+        name: "Empty",
+        storage: Some(storage),
+        calls: Some(PalletCallMetadata {
+          ty: meta_type::<Call>(),
+        }),
+        event: None,
+        constants: vec![],
+        error: None,
+        index: 0,
+      },
+      PalletMetadata {
+        name: "Test",
+        storage: None,
+        calls: Some(PalletCallMetadata {
+          ty: meta_type::<Call>(),
+        }),
+        event: Some(PalletEventMetadata {
+          ty: meta_type::<Mode>(),
+        }),
+        constants: vec![
+          constant("Small", 7u32),
+          constant("Large", u128::MAX),
+          constant(
+            "Limits",
+            Limits {
+              max: 5,
+              name: b"ab".to_vec(),
+            },
+          ),
+          constant("Off", Mode::Off),
+          constant("On", Mode::On(3)),
+          constant("Maybe", Some(9u16)),
+          constant("Pair", (true, -2i64)),
+          constant("Text", "hi".to_string()),
+        ],
+        error: None,
+        index: 1,
+      },
+    ];
+    let extrinsic = ExtrinsicMetadata {
+      ty: meta_type::<Vec<u8>>(),
+      version: 4,
+      signed_extensions: vec![],
+    };
+    let metadata = RuntimeMetadataV14::new(pallets, extrinsic, meta_type::<()>());
+    RuntimeMetadataPrefixed::from(metadata).encode()
+  }
+
+  #[test]
+  fn decodes_v14_constants() {
+    let pallets = decode_constants(&test_metadata()).unwrap();
+    assert_eq!(pallets.len(), 1);
+    assert_eq!(pallets[0].pallet, "Test");
+    let constants: Vec<_> = pallets[0]
+      .constants
+      .iter()
+      .map(|c| (c.name.as_str(), c.type_name.as_str(), c.value.clone()))
+      .collect();
+    assert_eq!(
+      constants,
+      [
+        ("Small", "u32", json!(7)),
+        ("Large", "u128", json!(u128::MAX.to_string())),
+        ("Limits", "Limits", json!({ "max": 5, "name": "0x6162" })),
+        ("Off", "Mode", json!("Off")),
+        ("On", "Mode", json!({ "On": 3 })),
+        ("Maybe", "Option", json!(9)),
+        ("Pair", "(bool, i64)", json!([true, -2])),
+        ("Text", "str", json!("hi")),
+      ]
+    );
+    assert_eq!(pallets[0].constants[0].docs, "First line.\n Second line.");
+  }
+
+  #[test]
+  fn rejects_other_metadata() {
+    let mut metadata = test_metadata();
+    metadata[4] = 15;
+    assert_eq!(
+      decode_constants(&metadata).err().as_deref(),
+      Some("Unsupported metadata version: 15")
+    );
+    assert_eq!(
+      decode_constants(&[0; 8]).err().as_deref(),
+      Some("Not runtime metadata")
+    );
+  }
+}
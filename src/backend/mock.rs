@@ -0,0 +1,64 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use super::{BackendApi, BackendEvent, BackendRequest, Result};
+
+/// Scripted backend that doesn't connect to a chain.
+///
+/// Queued events are returned by `next_update` and the frontend's requests are recorded, so the
+/// frontend can be driven deterministically.  Clones share the same event and request queues, so
+/// keep a clone to script a mock that was handed to `BackendState`.
+#[derive(Clone, Default)]
+pub struct MockBackend {
+  url: String,
+  archive_url: String,
+  events: Rc<RefCell<VecDeque<BackendEvent>>>,
+  requests: Rc<RefCell<Vec<BackendRequest>>>,
+}
+
+impl MockBackend {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Queue an event for the frontend.
+  pub fn push_event(&self, event: BackendEvent) {
+    self.events.borrow_mut().push_back(event);
+  }
+
+  /// Take the requests sent by the frontend so far.
+  pub fn take_requests(&self) -> Vec<BackendRequest> {
+    self.requests.take()
+  }
+}
+
+impl BackendApi for MockBackend {
+  fn get_url(&self) -> &str {
+    &self.url
+  }
+
+  fn connect_to(&mut self, url: &str) -> Result<()> {
+    self.url = url.to_string();
+    self.request(BackendRequest::ConnectTo(url.to_string()))
+  }
+
+  fn get_archive_url(&self) -> &str {
+    &self.archive_url
+  }
+
+  fn set_archive(&mut self, url: &str) -> Result<()> {
+    self.archive_url = url.to_string();
+    let url = Some(url.to_string()).filter(|url| !url.is_empty());
+    self.request(BackendRequest::SetArchive(url))
+  }
+
+  fn request(&self, req: BackendRequest) -> Result<()> {
+    self.requests.borrow_mut().push(req);
+    Ok(())
+  }
+
+  fn next_update(&mut self) -> Option<BackendEvent> {
+    self.events.borrow_mut().pop_front()
+  }
+}
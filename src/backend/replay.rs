@@ -0,0 +1,143 @@
+use std::collections::VecDeque;
+use std::io::Write;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{BackendApi, BackendEvent, BackendRequest, Result};
+
+/// Line of a recording.
+#[derive(Serialize, Deserialize)]
+struct RecordedEvent {
+  /// Milliseconds since the recording started.
+  at: i64,
+  event: BackendEvent,
+}
+
+enum RecordOutput {
+  File(std::fs::File),
+  Memory(Vec<u8>),
+}
+
+/// Records backend events as JSON lines, with their timing for replay.
+pub struct Recorder {
+  started: DateTime<Utc>,
+  events: usize,
+  out: RecordOutput,
+}
+
+impl Recorder {
+  fn new(out: RecordOutput) -> Self {
+    Self {
+      started: Utc::now(),
+      events: 0,
+      out,
+    }
+  }
+
+  /// Write the events to a file as they are recorded.
+  pub fn to_file(path: &str) -> Result<Self> {
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    Ok(Self::new(RecordOutput::File(file)))
+  }
+
+  /// Keep the recording in memory, see `recording`.
+  pub fn in_memory() -> Self {
+    Self::new(RecordOutput::Memory(Vec::new()))
+  }
+
+  pub fn record(&mut self, event: &BackendEvent) {
+    let at = (Utc::now() - self.started).num_milliseconds();
+    let line = match serde_json::to_string(&RecordedEvent {
+      at,
+      event: event.clone(),
+    }) {
+      Ok(line) => line,
+      Err(err) => {
+        log::error!("Failed to record backend event: {err:?}");
+        return;
+      }
+    };
+    let res = match &mut self.out {
+      RecordOutput::File(file) => writeln!(file, "{line}"),
+      RecordOutput::Memory(buf) => writeln!(buf, "{line}"),
+    };
+    match res {
+      Ok(()) => self.events += 1,
+      Err(err) => log::error!("Failed to record backend event: {err:?}"),
+    }
+  }
+
+  /// Number of recorded events.
+  pub fn events(&self) -> usize {
+    self.events
+  }
+
+  /// The recording, if it is kept in memory.
+  pub fn recording(&self) -> Option<&[u8]> {
+    match &self.out {
+      RecordOutput::Memory(buf) => Some(buf),
+      RecordOutput::File(_) => None,
+    }
+  }
+}
+
+/// Backend that plays back a recording made by [`Recorder`].
+pub struct ReplayBackend {
+  url: String,
+  started: DateTime<Utc>,
+  /// Playback speed, `2.0` replays twice as fast as recorded.
+  speed: f64,
+  events: VecDeque<RecordedEvent>,
+}
+
+impl ReplayBackend {
+  /// Parse a recording.
+  pub fn parse(recording: &str, speed: f64) -> Result<Self> {
+    let events = recording
+      .lines()
+      .filter(|line| !line.trim().is_empty())
+      .enumerate()
+      .map(|(idx, line)| serde_json::from_str(line).map_err(|e| format!("Line {}: {e}", idx + 1)))
+      .collect::<Result<_>>()?;
+    Ok(Self {
+      url: "replay".into(),
+      started: Utc::now(),
+      speed,
+      events,
+    })
+  }
+}
+
+impl BackendApi for ReplayBackend {
+  fn get_url(&self) -> &str {
+    &self.url
+  }
+
+  fn connect_to(&mut self, url: &str) -> Result<()> {
+    self.url = url.to_string();
+    Ok(())
+  }
+
+  fn get_archive_url(&self) -> &str {
+    ""
+  }
+
+  fn set_archive(&mut self, _url: &str) -> Result<()> {
+    Ok(())
+  }
+
+  fn request(&self, _req: BackendRequest) -> Result<()> {
+    // The recording already has the responses.
+    Ok(())
+  }
+
+  fn next_update(&mut self) -> Option<BackendEvent> {
+    let elapsed = (Utc::now() - self.started).num_milliseconds() as f64 * self.speed;
+    if self.events.front()?.at as f64 <= elapsed {
+      self.events.pop_front().map(|recorded| recorded.event)
+    } else {
+      None
+    }
+  }
+}
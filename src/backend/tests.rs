@@ -0,0 +1,367 @@
+use codec::Encode;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use tokio::net::{TcpListener, TcpStream};
+
+use super::*;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Header of a test block, decoded like the node's headers without digest logs.
+fn test_header(number: BlockNumber, parent: BlockHash) -> Header {
+  let mut data = parent.encode();
+  Compact(number).encode_to(&mut data);
+  data.extend([0; 65]);
+  Header::decode(&mut &data[..]).expect("valid header")
+}
+
+/// Answers JSON-RPC calls from a two block chain.  `chain_getFinalizedHead` hangs until
+/// `release` is set, like a node that is slow to answer a query.
+#[derive(Clone)]
+struct TestRpc {
+  chain: Vec<Header>,
+  /// Block with a header, but whose body was pruned.
+  pruned: Header,
+  release: watch::Receiver<bool>,
+  /// Number of `chain_getFinalizedHead` calls waiting for `release`.
+  hanging: watch::Sender<usize>,
+  /// Methods called, in order.
+  calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestRpc {
+  async fn answer(&mut self, method: &str, params: &Value) -> Value {
+    self.calls.lock().unwrap().push(method.to_string());
+    let best = &self.chain[self.chain.len() - 1];
+    let header = |hash: &Value| {
+      let hash: Option<BlockHash> = serde_json::from_value(hash.clone()).ok();
+      match hash {
+        Some(hash) => self
+          .chain
+          .iter()
+          .chain([&self.pruned])
+          .find(|header| header.hash() == hash),
+        None => Some(best),
+      }
+    };
+    match method {
+      "chain_getBlockHash" => {
+        let header = match params[0].as_u64() {
+          Some(number) => self.chain.get(number as usize),
+          None => Some(best),
+        };
+        to_value(header.map(|header| header.hash())).unwrap()
+      }
+      "chain_getHeader" => to_value(header(&params[0])).unwrap(),
+      "chain_getBlock" => match header(&params[0]) {
+        Some(header) if header.hash() != self.pruned.hash() => serde_json::json!({
+          "block": { "header": header, "extrinsics": [] },
+          "justifications": null,
+        }),
+        _ => Value::Null,
+      },
+      "chain_getFinalizedHead" => {
+        self.hanging.send_modify(|hanging| *hanging += 1);
+        while !*self.release.borrow() {
+          if self.release.changed().await.is_err() {
+            break;
+          }
+        }
+        to_value(best.hash()).unwrap()
+      }
+      "state_getRuntimeVersion" => serde_json::json!({ "specVersion": 1 }),
+      "system_properties" => serde_json::json!({ "ss58Format": 12 }),
+      _ => Value::Null,
+    }
+  }
+
+  /// Serve HTTP requests on a connection until the client closes it.
+  async fn serve(mut self, mut stream: TcpStream) {
+    let mut buf = Vec::new();
+    loop {
+      // The headers, then a body of `Content-Length` bytes.
+      let (head, head_len) = loop {
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+          break (String::from_utf8_lossy(&buf[..pos]).to_lowercase(), pos + 4);
+        }
+        if !read_more(&mut stream, &mut buf).await {
+          return;
+        }
+      };
+      let body_len = head
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|len| len.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+      while buf.len() < head_len + body_len {
+        if !read_more(&mut stream, &mut buf).await {
+          return;
+        }
+      }
+      let req: Value = serde_json::from_slice(&buf[head_len..head_len + body_len]).unwrap();
+      buf.drain(..head_len + body_len);
+      let method = req["method"].as_str().unwrap_or_default();
+      let result = self.answer(method, &req["params"]).await;
+      let body = serde_json::json!({ "jsonrpc": "2.0", "id": req["id"], "result": result });
+      let body = body.to_string();
+      let resp = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+      );
+      if stream.write_all(resp.as_bytes()).await.is_err() || head.contains("connection: close") {
+        return;
+      }
+    }
+  }
+}
+
+async fn read_more(stream: &mut TcpStream, buf: &mut Vec<u8>) -> bool {
+  let mut chunk = [0; 4096];
+  match stream.read(&mut chunk).await {
+    Ok(0) | Err(_) => false,
+    Ok(len) => {
+      buf.extend(&chunk[..len]);
+      true
+    }
+  }
+}
+
+/// HTTP node for the backend to connect to.
+struct TestNode {
+  url: String,
+  chain: Vec<Header>,
+  pruned: Header,
+  release: watch::Sender<bool>,
+  hanging: watch::Receiver<usize>,
+  calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestNode {
+  async fn start() -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let genesis = test_header(0, BlockHash::default());
+    let block = test_header(1, genesis.hash());
+    let pruned = test_header(2, block.hash());
+    let chain = vec![genesis, block];
+    let (release, release_rx) = watch::channel(false);
+    let (hanging_tx, hanging) = watch::channel(0);
+    let calls = Arc::default();
+    let rpc = TestRpc {
+      calls: Arc::clone(&calls),
+      chain: chain.clone(),
+      pruned: pruned.clone(),
+      release: release_rx,
+      hanging: hanging_tx,
+    };
+    tokio::spawn(async move {
+      while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(rpc.clone().serve(stream));
+      }
+    });
+    Self {
+      url,
+      chain,
+      pruned,
+      release,
+      hanging,
+      calls,
+    }
+  }
+
+  fn was_called(&self, method: &str) -> bool {
+    self.calls.lock().unwrap().iter().any(|call| call == method)
+  }
+
+  /// Wait until a query hangs on the node.
+  async fn query_hanging(&mut self) {
+    let hanging = self.hanging.wait_for(|hanging| *hanging > 0);
+    tokio::time::timeout(TIMEOUT, hanging)
+      .await
+      .unwrap()
+      .unwrap();
+  }
+}
+
+/// Receive events until one matches, returns all the received events.
+async fn wait_for(
+  event_rx: &mut BackendEventReceiver,
+  matches: impl Fn(&BackendEvent) -> bool,
+) -> Vec<BackendEvent> {
+  let mut events = Vec::new();
+  loop {
+    let event = tokio::time::timeout(TIMEOUT, event_rx.recv())
+      .await
+      .expect("timed out waiting for a backend event")
+      .expect("backend stopped");
+    let done = matches(&event);
+    events.push(event);
+    if done {
+      return events;
+    }
+  }
+}
+
+/// Start a backend connected to `node`, after it announced the chain head.
+async fn connect(node: &TestNode) -> (BackendRequestSender, BackendEventReceiver) {
+  let (event_tx, mut event_rx) = mpsc::channel(16);
+  let (req_tx, req_rx) = mpsc::channel(16);
+  tokio::spawn(SpawnBackend::new(req_rx, event_tx).run_backend());
+  req_tx
+    .send(BackendRequest::ConnectTo(node.url.clone()))
+    .await
+    .unwrap();
+  wait_for(&mut event_rx, |event| {
+    matches!(event, BackendEvent::NewHeader(_))
+  })
+  .await;
+  (req_tx, event_rx)
+}
+
+fn is_chain_status(event: &BackendEvent) -> bool {
+  matches!(event, BackendEvent::ChainStatus(_))
+}
+
+#[tokio::test]
+async fn slow_query_doesnt_delay_block_pushes() {
+  let mut node = TestNode::start().await;
+  let (req_tx, mut event_rx) = connect(&node).await;
+  req_tx.send(BackendRequest::GetChainStatus).await.unwrap();
+  node.query_hanging().await;
+
+  // The block is pushed while the query waits on the node.
+  let hash = node.chain[1].hash();
+  req_tx
+    .send(BackendRequest::GetBlockInfo(hash))
+    .await
+    .unwrap();
+  let events = wait_for(
+    &mut event_rx,
+    |event| matches!(event, BackendEvent::BlockInfo(block) if block.hash == hash),
+  )
+  .await;
+  assert!(!events.iter().any(is_chain_status));
+
+  // The query answers once the node does.
+  node.release.send(true).unwrap();
+  wait_for(&mut event_rx, is_chain_status).await;
+}
+
+#[tokio::test]
+async fn reconnect_cancels_queries() {
+  let mut node = TestNode::start().await;
+  let (req_tx, mut event_rx) = connect(&node).await;
+  req_tx.send(BackendRequest::GetChainStatus).await.unwrap();
+  node.query_hanging().await;
+
+  let mut events = Vec::new();
+  req_tx
+    .send(BackendRequest::ConnectTo(node.url.clone()))
+    .await
+    .unwrap();
+  events.extend(
+    wait_for(&mut event_rx, |event| {
+      matches!(
+        event,
+        BackendEvent::Connected {
+          is_reconnect: true,
+          ..
+        }
+      )
+    })
+    .await,
+  );
+
+  // The node answers the query from the old connection, but nobody waits for it anymore.
+  node.release.send(true).unwrap();
+  let hash = node.chain[1].hash();
+  req_tx
+    .send(BackendRequest::GetBlockInfo(hash))
+    .await
+    .unwrap();
+  events.extend(
+    wait_for(
+      &mut event_rx,
+      |event| matches!(event, BackendEvent::BlockInfo(block) if block.hash == hash),
+    )
+    .await,
+  );
+  sleep(Duration::from_millis(200)).await;
+  while let Ok(event) = event_rx.try_recv() {
+    events.push(event);
+  }
+  assert!(!events.iter().any(is_chain_status));
+}
+
+#[tokio::test]
+async fn failed_block_doesnt_reconnect() {
+  let node = TestNode::start().await;
+  let (req_tx, mut event_rx) = connect(&node).await;
+  req_tx
+    .send(BackendRequest::GetBlockInfo(node.pruned.hash()))
+    .await
+    .unwrap();
+  let mut events = wait_for(&mut event_rx, |event| {
+    matches!(event, BackendEvent::Error(_))
+  })
+  .await;
+
+  // The connection stays up and loads the next block.
+  let hash = node.chain[1].hash();
+  req_tx
+    .send(BackendRequest::GetBlockInfo(hash))
+    .await
+    .unwrap();
+  events.extend(
+    wait_for(
+      &mut event_rx,
+      |event| matches!(event, BackendEvent::BlockInfo(block) if block.hash == hash),
+    )
+    .await,
+  );
+  assert!(!events
+    .iter()
+    .any(|event| matches!(event, BackendEvent::Connected { .. })));
+}
+
+#[tokio::test]
+async fn header_only_block_doesnt_ask_for_the_spec_version() {
+  let node = TestNode::start().await;
+  let (req_tx, mut event_rx) = connect(&node).await;
+  req_tx
+    .send(BackendRequest::SetLazyEvents(true))
+    .await
+    .unwrap();
+  let hash = node.chain[1].hash();
+  req_tx
+    .send(BackendRequest::PreloadBlock(hash))
+    .await
+    .unwrap();
+  let events = wait_for(
+    &mut event_rx,
+    |event| matches!(event, BackendEvent::BlockInfo(block) if block.hash == hash),
+  )
+  .await;
+  let Some(BackendEvent::BlockInfo(block)) = events.last() else {
+    unreachable!();
+  };
+  assert!(block.header_only);
+  assert_eq!(block.spec_version, None);
+  assert!(!node.was_called("state_getRuntimeVersion"));
+
+  // The full block gets it.
+  req_tx
+    .send(BackendRequest::GetBlockInfo(hash))
+    .await
+    .unwrap();
+  let events = wait_for(
+    &mut event_rx,
+    |event| matches!(event, BackendEvent::BlockInfo(block) if block.hash == hash),
+  )
+  .await;
+  let Some(BackendEvent::BlockInfo(block)) = events.last() else {
+    unreachable!();
+  };
+  assert_eq!(block.spec_version, Some(1));
+}
@@ -1,6 +1,6 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
-pub use app::PolymeshApp;
+pub use app::{BackendState, BlockEventSummary, PolymeshApp};
 
-mod backend;
+pub mod backend;